@@ -0,0 +1,348 @@
+use std::fmt;
+
+use crate::*;
+
+/// An error in the arguments of a [`QueryRequest`], found by [`validate_arguments`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArgumentError {
+    /// The request's collection is neither a collection nor a function in the schema
+    UnknownCollection { collection: CollectionName },
+    /// An argument was provided which the collection does not accept
+    UnknownArgument {
+        collection: CollectionName,
+        argument: ArgumentName,
+    },
+    /// A non-nullable argument was not provided
+    MissingArgument {
+        collection: CollectionName,
+        argument: ArgumentName,
+    },
+}
+
+impl fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgumentError::UnknownCollection { collection } => {
+                write!(f, "collection {collection} is not defined")
+            }
+            ArgumentError::UnknownArgument {
+                collection,
+                argument,
+            } => write!(f, "collection {collection} has no argument {argument}"),
+            ArgumentError::MissingArgument {
+                collection,
+                argument,
+            } => write!(
+                f,
+                "required argument {argument} of collection {collection} was not provided"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArgumentError {}
+
+/// An error converting a value into procedure arguments, from
+/// [`MutationOperation::with_args`]
+#[derive(Debug)]
+pub enum ProcedureArgumentsError {
+    /// The value could not be serialized
+    Serialization(serde_json::Error),
+    /// The value did not serialize to a JSON object, so has no named arguments
+    NotAnObject { actual: &'static str },
+}
+
+impl fmt::Display for ProcedureArgumentsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcedureArgumentsError::Serialization(e) => {
+                write!(f, "procedure arguments could not be serialized: {e}")
+            }
+            ProcedureArgumentsError::NotAnObject { actual } => write!(
+                f,
+                "procedure arguments must serialize to an object, but serialized to {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProcedureArgumentsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProcedureArgumentsError::Serialization(e) => Some(e),
+            ProcedureArgumentsError::NotAnObject { .. } => None,
+        }
+    }
+}
+
+/// Check the arguments of a query request against the schema, before sending it to
+/// the connector. Every argument provided must be defined on the target collection
+/// or function, and every argument with a non-nullable type must be provided.
+///
+/// The types of argument values are not checked.
+pub fn validate_arguments(
+    request: &QueryRequest,
+    schema: &SchemaResponse,
+) -> Result<(), ArgumentError> {
+    let collection = &request.collection;
+    let arguments = schema
+        .collections
+        .iter()
+        .find(|info| info.name == *collection)
+        .map(|info| &info.arguments)
+        .or_else(|| {
+            schema
+                .functions
+                .iter()
+                .find(|info| info.name.as_str() == collection.as_str())
+                .map(|info| &info.arguments)
+        })
+        .ok_or_else(|| ArgumentError::UnknownCollection {
+            collection: collection.clone(),
+        })?;
+
+    if let Some(argument) = request
+        .arguments
+        .keys()
+        .find(|argument| !arguments.contains_key(*argument))
+    {
+        return Err(ArgumentError::UnknownArgument {
+            collection: collection.clone(),
+            argument: argument.clone(),
+        });
+    }
+
+    for (argument, info) in arguments {
+        let required = !matches!(info.argument_type, Type::Nullable { .. });
+        if required && !request.arguments.contains_key(argument) {
+            return Err(ArgumentError::MissingArgument {
+                collection: collection.clone(),
+                argument: argument.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl Argument {
+    /// A literal argument, from any value which converts to JSON, for example
+    /// `Argument::literal(30)` or `Argument::literal("Alice")`
+    pub fn literal(value: impl Into<serde_json::Value>) -> Self {
+        Argument::Literal {
+            value: value.into(),
+        }
+    }
+
+    /// An argument which refers to a variable
+    pub fn variable(name: impl Into<VariableName>) -> Self {
+        Argument::Variable { name: name.into() }
+    }
+}
+
+impl From<serde_json::Value> for Argument {
+    fn from(value: serde_json::Value) -> Self {
+        Argument::Literal { value }
+    }
+}
+
+impl RelationshipArgument {
+    /// A literal argument, from any value which converts to JSON
+    pub fn literal(value: impl Into<serde_json::Value>) -> Self {
+        RelationshipArgument::Literal {
+            value: value.into(),
+        }
+    }
+
+    /// An argument which refers to a variable
+    pub fn variable(name: impl Into<VariableName>) -> Self {
+        RelationshipArgument::Variable { name: name.into() }
+    }
+}
+
+impl MutationOperation {
+    /// A procedure call whose arguments are the fields of `args`, which must serialize
+    /// to a JSON object, for example a `#[derive(Serialize)]` struct. The result of the
+    /// procedure is returned in full.
+    pub fn with_args<T: Serialize>(
+        name: impl Into<ProcedureName>,
+        args: T,
+    ) -> Result<Self, ProcedureArgumentsError> {
+        let arguments =
+            match serde_json::to_value(args).map_err(ProcedureArgumentsError::Serialization)? {
+                serde_json::Value::Object(object) => object
+                    .into_iter()
+                    .map(|(name, value)| (name.into(), value))
+                    .collect(),
+                value => {
+                    return Err(ProcedureArgumentsError::NotAnObject {
+                        actual: crate::values::json_type(&value),
+                    })
+                }
+            };
+        Ok(MutationOperation::Procedure {
+            name: name.into(),
+            arguments,
+            fields: None,
+        })
+    }
+}
+
+impl From<serde_json::Value> for RelationshipArgument {
+    fn from(value: serde_json::Value) -> Self {
+        RelationshipArgument::Literal { value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_mutation_operation_with_args() {
+        #[derive(Serialize)]
+        struct CreateUser {
+            name: &'static str,
+            age: u32,
+        }
+
+        let operation = MutationOperation::with_args(
+            "create_user",
+            CreateUser {
+                name: "Alice",
+                age: 30,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            operation,
+            MutationOperation::Procedure {
+                name: "create_user".into(),
+                arguments: BTreeMap::from([
+                    ("age".into(), serde_json::json!(30)),
+                    ("name".into(), serde_json::json!("Alice")),
+                ]),
+                fields: None,
+            }
+        );
+
+        assert!(matches!(
+            MutationOperation::with_args("create_user", 30),
+            Err(ProcedureArgumentsError::NotAnObject { actual: "a number" })
+        ));
+    }
+
+    #[test]
+    fn test_argument_constructors() {
+        assert_eq!(
+            Argument::literal(30),
+            Argument::Literal {
+                value: serde_json::json!(30)
+            }
+        );
+        assert_eq!(
+            Argument::literal("Alice"),
+            Argument::from(serde_json::json!("Alice"))
+        );
+        assert_eq!(
+            RelationshipArgument::variable("author_id"),
+            RelationshipArgument::Variable {
+                name: "author_id".into()
+            }
+        );
+
+        // Procedure arguments are plain JSON values, which already convert from
+        // native types
+        let mut arguments = BTreeMap::<ArgumentName, serde_json::Value>::new();
+        arguments.insert("age".into(), 30.into());
+        assert_eq!(arguments[&ArgumentName::from("age")], serde_json::json!(30));
+    }
+
+    fn schema() -> SchemaResponse {
+        let argument = |argument_type| ArgumentInfo {
+            description: None,
+            argument_type,
+        };
+        let int = Type::Named { name: "Int".into() };
+
+        SchemaResponse {
+            functions: vec![FunctionInfo {
+                name: "latest_article".into(),
+                description: None,
+                arguments: BTreeMap::from([
+                    ("author_id".into(), argument(int.clone())),
+                    (
+                        "limit".into(),
+                        argument(Type::Nullable {
+                            underlying_type: Box::new(int.clone()),
+                        }),
+                    ),
+                ]),
+                result_type: int,
+            }],
+            ..SchemaResponse::default()
+        }
+    }
+
+    fn request(arguments: &[&str]) -> QueryRequest {
+        QueryRequest {
+            collection: "latest_article".into(),
+            query: Query {
+                aggregates: None,
+                fields: None,
+                limit: None,
+                offset: None,
+                order_by: None,
+                predicate: None,
+            },
+            arguments: arguments
+                .iter()
+                .map(|name| {
+                    (
+                        (*name).into(),
+                        Argument::Literal {
+                            value: serde_json::json!(1),
+                        },
+                    )
+                })
+                .collect(),
+            collection_relationships: BTreeMap::new(),
+            variables: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_arguments() {
+        let schema = schema();
+
+        assert_eq!(
+            validate_arguments(&request(&["author_id"]), &schema),
+            Ok(())
+        );
+        assert_eq!(
+            validate_arguments(&request(&["author_id", "limit"]), &schema),
+            Ok(())
+        );
+        assert_eq!(
+            validate_arguments(&request(&[]), &schema),
+            Err(ArgumentError::MissingArgument {
+                collection: "latest_article".into(),
+                argument: "author_id".into(),
+            })
+        );
+        assert_eq!(
+            validate_arguments(&request(&["author_id", "offset"]), &schema),
+            Err(ArgumentError::UnknownArgument {
+                collection: "latest_article".into(),
+                argument: "offset".into(),
+            })
+        );
+
+        let mut unknown = request(&[]);
+        unknown.collection = "articles".into();
+        assert!(matches!(
+            validate_arguments(&unknown, &schema),
+            Err(ArgumentError::UnknownCollection { .. })
+        ));
+    }
+}
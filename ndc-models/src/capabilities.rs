@@ -0,0 +1,180 @@
+use std::fmt;
+
+use crate::*;
+
+/// The differences between two [`CapabilitiesResponse`]s, for example before and
+/// after upgrading a connector
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapabilitiesDiff {
+    /// The old and new specification versions, if the version changed
+    pub version: Option<(String, String)>,
+    /// Features supported by the new connector, but not the old one
+    pub added: Vec<&'static str>,
+    /// Features supported by the old connector, but not the new one
+    pub removed: Vec<&'static str>,
+}
+
+impl CapabilitiesDiff {
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none() && self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for CapabilitiesDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let mut lines = vec![];
+        if let Some((old, new)) = &self.version {
+            lines.push(format!("version: {old} -> {new}"));
+        }
+        lines.extend(self.added.iter().map(|feature| format!("+ {feature}")));
+        lines.extend(self.removed.iter().map(|feature| format!("- {feature}")));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Compare the capabilities of two versions of a connector
+pub fn capabilities_diff(
+    old: &CapabilitiesResponse,
+    new: &CapabilitiesResponse,
+) -> CapabilitiesDiff {
+    let old_features = old.capabilities.supported_features();
+    let new_features = new.capabilities.supported_features();
+
+    CapabilitiesDiff {
+        version: (old.version != new.version).then(|| (old.version.clone(), new.version.clone())),
+        added: new_features
+            .iter()
+            .filter(|feature| !old_features.contains(feature))
+            .copied()
+            .collect(),
+        removed: old_features
+            .iter()
+            .filter(|feature| !new_features.contains(feature))
+            .copied()
+            .collect(),
+    }
+}
+
+impl Capabilities {
+    /// The names of the optional features which this connector supports, as dotted
+    /// paths into the capabilities object, for example `query.aggregates`.
+    pub fn supported_features(&self) -> Vec<&'static str> {
+        let mut features = vec![];
+        let mut add = |supported: bool, name| {
+            if supported {
+                features.push(name);
+            }
+        };
+
+        let query = &self.query;
+        add(query.aggregates.is_some(), "query.aggregates");
+        add(query.variables.is_some(), "query.variables");
+        add(query.explain.is_some(), "query.explain");
+        add(
+            query.nested_fields.filter_by.is_some(),
+            "query.nested_fields.filter_by",
+        );
+        add(
+            query.nested_fields.order_by.is_some(),
+            "query.nested_fields.order_by",
+        );
+        add(
+            query.nested_fields.aggregates.is_some(),
+            "query.nested_fields.aggregates",
+        );
+
+        add(
+            self.mutation.transactional.is_some(),
+            "mutation.transactional",
+        );
+        add(self.mutation.explain.is_some(), "mutation.explain");
+
+        add(self.relationships.is_some(), "relationships");
+        if let Some(relationships) = &self.relationships {
+            add(
+                relationships.relation_comparisons.is_some(),
+                "relationships.relation_comparisons",
+            );
+            add(
+                relationships.order_by_aggregate.is_some(),
+                "relationships.order_by_aggregate",
+            );
+        }
+
+        features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            query: QueryCapabilities {
+                aggregates: Some(LeafCapability {}),
+                variables: None,
+                explain: None,
+                nested_fields: NestedFieldCapabilities {
+                    filter_by: Some(LeafCapability {}),
+                    order_by: None,
+                    aggregates: None,
+                },
+            },
+            mutation: MutationCapabilities {
+                transactional: None,
+                explain: Some(LeafCapability {}),
+            },
+            relationships: Some(RelationshipCapabilities {
+                relation_comparisons: None,
+                order_by_aggregate: Some(LeafCapability {}),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_supported_features() {
+        assert_eq!(
+            capabilities().supported_features(),
+            vec![
+                "query.aggregates",
+                "query.nested_fields.filter_by",
+                "mutation.explain",
+                "relationships",
+                "relationships.order_by_aggregate",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capabilities_diff() {
+        let old = CapabilitiesResponse {
+            version: "0.1.0".into(),
+            capabilities: capabilities(),
+        };
+        let mut new = old.clone();
+        assert!(capabilities_diff(&old, &new).is_empty());
+
+        new.version = "0.1.1".into();
+        new.capabilities.query.variables = Some(LeafCapability {});
+        new.capabilities.relationships = None;
+
+        let diff = capabilities_diff(&old, &new);
+        assert_eq!(
+            diff,
+            CapabilitiesDiff {
+                version: Some(("0.1.0".into(), "0.1.1".into())),
+                added: vec!["query.variables"],
+                removed: vec!["relationships", "relationships.order_by_aggregate"],
+            }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "version: 0.1.0 -> 0.1.1\n+ query.variables\n- relationships\n- relationships.order_by_aggregate"
+        );
+    }
+}
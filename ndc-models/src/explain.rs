@@ -0,0 +1,76 @@
+use crate::*;
+
+impl ExplainResponse {
+    /// The number of rows the connector expects the query to return, if the details
+    /// include an `estimated_rows` entry which is a non-negative integer.
+    ///
+    /// Keys are matched ignoring case, and treating spaces and hyphens as
+    /// underscores, so `Estimated Rows` is also recognized.
+    pub fn estimated_rows(&self) -> Option<u64> {
+        self.detail("estimated_rows")?.trim().parse().ok()
+    }
+
+    /// The connector's estimate of the cost of the query, if the details include an
+    /// `estimated_cost` entry which is a number. Costs are only comparable between
+    /// plans from the same connector.
+    pub fn estimated_cost(&self) -> Option<f64> {
+        self.detail("estimated_cost")?
+            .trim()
+            .parse()
+            .ok()
+            .filter(|cost: &f64| cost.is_finite())
+    }
+
+    fn detail(&self, key: &str) -> Option<&str> {
+        self.details
+            .iter()
+            .find(|(name, _)| normalize_key(name) == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    key.trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn explain(details: &[(&str, &str)]) -> ExplainResponse {
+        ExplainResponse {
+            details: details
+                .iter()
+                .map(|(key, value)| ((*key).into(), (*value).into()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_estimates() {
+        let response = explain(&[
+            ("SQL Query", "SELECT * FROM articles"),
+            ("estimated_rows", "42"),
+            ("Estimated Cost", " 12.5 "),
+        ]);
+        assert_eq!(response.estimated_rows(), Some(42));
+        assert_eq!(response.estimated_cost(), Some(12.5));
+    }
+
+    #[test]
+    fn test_missing_or_invalid_estimates() {
+        let response = explain(&[("SQL Query", "SELECT * FROM articles")]);
+        assert_eq!(response.estimated_rows(), None);
+        assert_eq!(response.estimated_cost(), None);
+
+        let response = explain(&[("estimated-rows", "-1"), ("estimated_cost", "NaN")]);
+        assert_eq!(response.estimated_rows(), None);
+        assert_eq!(response.estimated_cost(), None);
+    }
+}
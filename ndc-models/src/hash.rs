@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+use crate::*;
+
+impl SchemaResponse {
+    /// A SHA-256 hash of the schema, as lowercase hex, for detecting changes and as a
+    /// cache key. The hash is computed over the [`canonicalize`]d JSON form of the
+    /// schema, so it is stable across processes and restarts. The order of
+    /// collections, functions and procedures is significant.
+    pub fn hash(&self) -> String {
+        // Schemas only contain maps with string keys, so serialization can't fail
+        let value = serde_json::to_value(self).unwrap_or_default();
+        let bytes = serde_json::to_vec(&canonicalize(value)).unwrap_or_default();
+        Sha256::digest(bytes)
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
+    }
+}
+
+/// Sort the keys of every object in a JSON value, so that equal values always
+/// serialize to the same bytes, whatever order their maps were built in
+pub fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.into_iter().collect();
+            fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(canonicalize).collect())
+        }
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn object_type(fields: &[&str]) -> ObjectType {
+        ObjectType {
+            description: None,
+            fields: fields
+                .iter()
+                .map(|name| {
+                    (
+                        FieldName::from(*name),
+                        ObjectField {
+                            description: None,
+                            r#type: Type::Named { name: "Int".into() },
+                            arguments: BTreeMap::new(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_schema_hash() {
+        let mut schema = SchemaResponse::default();
+        schema
+            .object_types
+            .insert("article".into(), object_type(&["id", "author_id"]));
+        let hash = schema.hash();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, schema.hash());
+
+        let mut changed = schema.clone();
+        changed
+            .object_types
+            .insert("author".into(), object_type(&["id"]));
+        assert_ne!(changed.hash(), hash);
+
+        // Unlike maps, lists are ordered
+        let function = |name: &str| FunctionInfo {
+            name: name.into(),
+            description: None,
+            arguments: BTreeMap::new(),
+            result_type: Type::Named { name: "Int".into() },
+        };
+        let mut reordered = schema.clone();
+        schema.functions = vec![function("a"), function("b")];
+        reordered.functions = vec![function("b"), function("a")];
+        assert_ne!(reordered.hash(), schema.hash());
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        // With `preserve_order`, JSON objects keep their keys in insertion order
+        let value = serde_json::json!({ "b": 1, "a": [{ "d": 2, "c": 3 }] });
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"b":1,"a":[{"d":2,"c":3}]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&canonicalize(value)).unwrap(),
+            r#"{"a":[{"c":3,"d":2}],"b":1}"#
+        );
+    }
+}
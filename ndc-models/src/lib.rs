@@ -9,6 +9,34 @@ use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use smol_str::SmolStr;
 
+mod arguments;
+mod capabilities;
+mod explain;
+mod hash;
+mod order_by;
+mod redact;
+#[cfg(test)]
+mod round_trip;
+mod schema_diff;
+mod size;
+mod summary;
+mod template;
+mod validation;
+mod values;
+mod variables;
+mod visit;
+
+pub use arguments::{validate_arguments, ArgumentError, ProcedureArgumentsError};
+pub use capabilities::{capabilities_diff, CapabilitiesDiff};
+pub use hash::canonicalize;
+pub use order_by::{validate_order_by, OrderByError};
+pub use redact::REDACTED;
+pub use schema_diff::{schema_diff, SchemaChange, SchemaChangeKind, SchemaDiff};
+pub use template::TemplateError;
+pub use validation::{validate_schema, SchemaError};
+pub use values::{AggregateError, FieldTypeError};
+pub use variables::{explode_variables, reassemble_row_sets, referenced_variables, VariablesError};
+
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // ANCHOR: ErrorResponse
@@ -1044,6 +1072,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_round_trips() {
+        let fixtures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("ndc-reference")
+            .join("tests");
+
+        test_round_trip::<CapabilitiesResponse>(&fixtures_dir.join("capabilities/expected.json"));
+        test_round_trip::<SchemaResponse>(&fixtures_dir.join("schema/expected.json"));
+
+        for (dir, is_query) in [("query", true), ("mutation", false)] {
+            for entry in std::fs::read_dir(fixtures_dir.join(dir)).unwrap() {
+                let test_dir = entry.unwrap().path();
+                if is_query {
+                    test_round_trip::<QueryRequest>(&test_dir.join("request.json"));
+                    test_round_trip::<QueryResponse>(&test_dir.join("expected.json"));
+                } else {
+                    test_round_trip::<MutationRequest>(&test_dir.join("request.json"));
+                    test_round_trip::<MutationResponse>(&test_dir.join("expected.json"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_query_request_variables_wire_form() {
+        let request = |variables| QueryRequest {
+            collection: "articles".into(),
+            query: Query {
+                aggregates: None,
+                fields: None,
+                limit: None,
+                offset: None,
+                order_by: None,
+                predicate: None,
+            },
+            arguments: BTreeMap::new(),
+            collection_relationships: BTreeMap::new(),
+            variables,
+        };
+
+        // Absent variables mean a single row set, and the field is omitted
+        let absent = serde_json::to_value(request(None)).unwrap();
+        assert!(!absent.as_object().unwrap().contains_key("variables"));
+        assert_eq!(
+            serde_json::from_value::<QueryRequest>(absent).unwrap(),
+            request(None)
+        );
+
+        // Empty variables mean zero row sets, and must stay on the wire
+        let empty = serde_json::to_value(request(Some(vec![]))).unwrap();
+        assert_eq!(empty["variables"], serde_json::json!([]));
+        assert_eq!(
+            serde_json::from_value::<QueryRequest>(empty).unwrap(),
+            request(Some(vec![]))
+        );
+
+        // An explicit null is the same as an absent field
+        let mut null = serde_json::to_value(request(None)).unwrap();
+        null["variables"] = serde_json::Value::Null;
+        assert_eq!(
+            serde_json::from_value::<QueryRequest>(null).unwrap(),
+            request(None)
+        );
+    }
+
+    /// Check that a fixture survives being serialized and deserialized again, which
+    /// catches serde attributes that only apply in one direction
+    fn test_round_trip<T>(path: &std::path::Path)
+    where
+        T: serde::de::DeserializeOwned + Serialize + PartialEq + std::fmt::Debug,
+    {
+        let contents = std::fs::read_to_string(path).unwrap();
+        let value: T = serde_json::from_str(&contents)
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        let round_tripped: T = serde_json::from_value(serde_json::to_value(&value).unwrap())
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        assert_eq!(value, round_tripped, "{}", path.display());
+    }
+
     fn test_json_schema(mint: &mut Mint, schema: schemars::schema::RootSchema, filename: &str) {
         let expected_path = PathBuf::from_iter(["json_schema", filename]);
 
@@ -0,0 +1,422 @@
+use std::fmt;
+
+use crate::*;
+
+/// An error in an `order_by` of a [`QueryRequest`], found by [`validate_order_by`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OrderByError {
+    /// A collection being ordered, or reached through a relationship, is neither a
+    /// collection nor a function in the schema
+    UnknownCollection { collection: CollectionName },
+    /// A path refers to a relationship which is not in the request's
+    /// `collection_relationships`
+    UnknownRelationship { relationship: RelationshipName },
+    /// A column is not defined on the collection's object type
+    UnknownColumn {
+        collection: CollectionName,
+        column: FieldName,
+    },
+    /// A field path does not lead through object types to a defined field
+    UnknownNestedField {
+        collection: CollectionName,
+        column: FieldName,
+        field: FieldName,
+    },
+    /// An aggregate function is not defined on the scalar type of the column
+    UnknownAggregateFunction {
+        collection: CollectionName,
+        column: FieldName,
+        function: AggregateFunctionName,
+    },
+}
+
+impl fmt::Display for OrderByError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderByError::UnknownCollection { collection } => {
+                write!(f, "collection {collection} is not defined")
+            }
+            OrderByError::UnknownRelationship { relationship } => {
+                write!(f, "relationship {relationship} is not defined")
+            }
+            OrderByError::UnknownColumn { collection, column } => {
+                write!(f, "collection {collection} has no column {column}")
+            }
+            OrderByError::UnknownNestedField {
+                collection,
+                column,
+                field,
+            } => write!(
+                f,
+                "column {column} of collection {collection} has no nested field {field}"
+            ),
+            OrderByError::UnknownAggregateFunction {
+                collection,
+                column,
+                function,
+            } => write!(
+                f,
+                "aggregate function {function} is not defined for column {column} \
+                 of collection {collection}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OrderByError {}
+
+/// Check every `order_by` in a query request against the schema, before sending it
+/// to the connector, including those of queries nested in relationship fields.
+/// Every relationship in a path must be defined in the request, and every column,
+/// nested field and aggregate function must be defined in the schema, for the
+/// collection the path leads to.
+///
+/// Predicates in path elements are not checked.
+pub fn validate_order_by(
+    request: &QueryRequest,
+    schema: &SchemaResponse,
+) -> Result<(), OrderByError> {
+    let validator = Validator { request, schema };
+    validator.query(&request.collection, &request.query)
+}
+
+struct Validator<'a> {
+    request: &'a QueryRequest,
+    schema: &'a SchemaResponse,
+}
+
+impl<'a> Validator<'a> {
+    fn query(&self, collection: &'a CollectionName, query: &'a Query) -> Result<(), OrderByError> {
+        for element in query
+            .order_by
+            .iter()
+            .flat_map(|order_by| &order_by.elements)
+        {
+            self.target(collection, &element.target)?;
+        }
+        for field in query.fields.iter().flat_map(IndexMap::values) {
+            self.field(field)?;
+        }
+        Ok(())
+    }
+
+    fn field(&self, field: &'a Field) -> Result<(), OrderByError> {
+        match field {
+            Field::Column { fields, .. } => match fields {
+                Some(fields) => self.nested_field(fields),
+                None => Ok(()),
+            },
+            Field::Relationship {
+                query,
+                relationship,
+                ..
+            } => {
+                let target = &self.relationship(relationship)?.target_collection;
+                self.query(target, query)
+            }
+        }
+    }
+
+    fn nested_field(&self, field: &'a NestedField) -> Result<(), OrderByError> {
+        match field {
+            NestedField::Object(object) => {
+                for field in object.fields.values() {
+                    self.field(field)?;
+                }
+                Ok(())
+            }
+            NestedField::Array(array) => self.nested_field(&array.fields),
+        }
+    }
+
+    fn target(
+        &self,
+        collection: &'a CollectionName,
+        target: &OrderByTarget,
+    ) -> Result<(), OrderByError> {
+        match target {
+            OrderByTarget::Column {
+                name,
+                field_path,
+                path,
+            } => {
+                let collection = self.path(collection, path)?;
+                self.column_type(collection, name, field_path.as_deref())?;
+            }
+            OrderByTarget::SingleColumnAggregate {
+                column,
+                field_path,
+                function,
+                path,
+            } => {
+                let collection = self.path(collection, path)?;
+                let column_type = self.column_type(collection, column, field_path.as_deref())?;
+                let defined = match unwrap_nullable(column_type) {
+                    Type::Named { name } => self
+                        .schema
+                        .scalar_types
+                        .get(name.as_str())
+                        .is_some_and(|scalar| scalar.aggregate_functions.contains_key(function)),
+                    _ => false,
+                };
+                if !defined {
+                    return Err(OrderByError::UnknownAggregateFunction {
+                        collection: collection.clone(),
+                        column: column.clone(),
+                        function: function.clone(),
+                    });
+                }
+            }
+            OrderByTarget::StarCountAggregate { path } => {
+                self.path(collection, path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Follow a path of relationships, returning the collection it leads to
+    fn path(
+        &self,
+        collection: &'a CollectionName,
+        path: &[PathElement],
+    ) -> Result<&'a CollectionName, OrderByError> {
+        path.iter().try_fold(collection, |_, element| {
+            Ok(&self.relationship(&element.relationship)?.target_collection)
+        })
+    }
+
+    fn relationship(
+        &self,
+        relationship: &RelationshipName,
+    ) -> Result<&'a Relationship, OrderByError> {
+        self.request
+            .collection_relationships
+            .get(relationship)
+            .ok_or_else(|| OrderByError::UnknownRelationship {
+                relationship: relationship.clone(),
+            })
+    }
+
+    /// The type of a column of `collection`, or of the nested field at the end of
+    /// `field_path` within it
+    fn column_type(
+        &self,
+        collection: &CollectionName,
+        column: &FieldName,
+        field_path: Option<&[FieldName]>,
+    ) -> Result<&'a Type, OrderByError> {
+        let column_type =
+            self.column(collection, column)?
+                .ok_or_else(|| OrderByError::UnknownColumn {
+                    collection: collection.clone(),
+                    column: column.clone(),
+                })?;
+
+        field_path
+            .unwrap_or_default()
+            .iter()
+            .try_fold(column_type, |field_type, field| {
+                let object_type = match unwrap_nullable(field_type) {
+                    Type::Named { name } => self.schema.object_types.get(name.as_str()),
+                    _ => None,
+                };
+                object_type
+                    .and_then(|object_type| object_type.fields.get(field))
+                    .map(|object_field| &object_field.r#type)
+                    .ok_or_else(|| OrderByError::UnknownNestedField {
+                        collection: collection.clone(),
+                        column: column.clone(),
+                        field: field.clone(),
+                    })
+            })
+    }
+
+    /// The type of a column of a collection or function, or `None` if it has no such
+    /// column. A function's rows have a single column, `__value`.
+    fn column(
+        &self,
+        collection: &CollectionName,
+        column: &FieldName,
+    ) -> Result<Option<&'a Type>, OrderByError> {
+        if let Some(info) = self
+            .schema
+            .collections
+            .iter()
+            .find(|info| info.name == *collection)
+        {
+            return Ok(self
+                .schema
+                .object_types
+                .get(&info.collection_type)
+                .and_then(|object_type| object_type.fields.get(column))
+                .map(|object_field| &object_field.r#type));
+        }
+
+        if let Some(info) = self
+            .schema
+            .functions
+            .iter()
+            .find(|info| info.name.as_str() == collection.as_str())
+        {
+            return Ok((column.as_str() == "__value").then_some(&info.result_type));
+        }
+
+        Err(OrderByError::UnknownCollection {
+            collection: collection.clone(),
+        })
+    }
+}
+
+fn unwrap_nullable(mut r#type: &Type) -> &Type {
+    while let Type::Nullable { underlying_type } = r#type {
+        r#type = underlying_type;
+    }
+    r#type
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::*;
+
+    fn schema() -> SchemaResponse {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../ndc-reference/tests/schema/expected.json");
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn request(collection: &str, target: serde_json::Value) -> QueryRequest {
+        serde_json::from_value(serde_json::json!({
+            "collection": collection,
+            "arguments": {},
+            "query": {
+                "order_by": {
+                    "elements": [{ "order_direction": "asc", "target": target }]
+                }
+            },
+            "collection_relationships": {
+                "article_author": {
+                    "column_mapping": { "author_id": "id" },
+                    "relationship_type": "object",
+                    "target_collection": "authors",
+                    "arguments": {}
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn relationship(name: &str) -> serde_json::Value {
+        serde_json::json!({ "relationship": name, "arguments": {} })
+    }
+
+    #[test]
+    fn test_validate_order_by() {
+        let schema = schema();
+        let validate =
+            |collection, target| validate_order_by(&request(collection, target), &schema);
+
+        assert_eq!(
+            validate(
+                "articles",
+                serde_json::json!({ "type": "column", "name": "title", "path": [] })
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate(
+                "articles",
+                serde_json::json!({
+                    "type": "column",
+                    "name": "last_name",
+                    "path": [relationship("article_author")]
+                })
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate(
+                "institutions",
+                serde_json::json!({
+                    "type": "column",
+                    "name": "location",
+                    "field_path": ["city"],
+                    "path": []
+                })
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate(
+                "latest_article_id",
+                serde_json::json!({ "type": "column", "name": "__value", "path": [] })
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate(
+                "articles",
+                serde_json::json!({ "type": "column", "name": "rating", "path": [] })
+            ),
+            Err(OrderByError::UnknownColumn {
+                collection: "articles".into(),
+                column: "rating".into()
+            })
+        );
+        assert_eq!(
+            validate(
+                "articles",
+                serde_json::json!({
+                    "type": "star_count_aggregate",
+                    "path": [relationship("article_editor")]
+                })
+            ),
+            Err(OrderByError::UnknownRelationship {
+                relationship: "article_editor".into()
+            })
+        );
+        assert_eq!(
+            validate(
+                "institutions",
+                serde_json::json!({
+                    "type": "column",
+                    "name": "location",
+                    "field_path": ["postcode"],
+                    "path": []
+                })
+            ),
+            Err(OrderByError::UnknownNestedField {
+                collection: "institutions".into(),
+                column: "location".into(),
+                field: "postcode".into()
+            })
+        );
+        assert_eq!(
+            validate(
+                "articles",
+                serde_json::json!({
+                    "type": "single_column_aggregate",
+                    "column": "id",
+                    "function": "avg",
+                    "path": [relationship("article_author")]
+                })
+            ),
+            Err(OrderByError::UnknownAggregateFunction {
+                collection: "authors".into(),
+                column: "id".into(),
+                function: "avg".into()
+            })
+        );
+        assert_eq!(
+            validate(
+                "posts",
+                serde_json::json!({ "type": "column", "name": "id", "path": [] })
+            ),
+            Err(OrderByError::UnknownCollection {
+                collection: "posts".into()
+            })
+        );
+    }
+}
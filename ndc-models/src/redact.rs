@@ -0,0 +1,257 @@
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+
+use crate::visit::RequestVisitor;
+use crate::*;
+
+/// The value which replaces redacted values
+pub const REDACTED: &str = "***";
+
+impl QueryRequest {
+    /// A copy of the request which is safe to log, with the values of sensitive
+    /// arguments, variables and fields replaced by [`REDACTED`].
+    ///
+    /// A value is redacted if it is given for an argument or variable named in
+    /// `redact_fields`, if it is compared against a column (or nested field) named in
+    /// `redact_fields`, or if it is stored under a key named in `redact_fields`
+    /// anywhere inside a literal object. The values of a variable are redacted in
+    /// every variable set if the variable is given for such an argument or compared
+    /// against such a column.
+    pub fn redacted(&self, redact_fields: &[&str]) -> QueryRequest {
+        let mut redactor = Redactor {
+            redact_fields,
+            sensitive_variables: BTreeSet::new(),
+        };
+        let mut request = self.clone();
+        match redactor.request(&mut request) {
+            Ok(()) => {}
+            Err(never) => match never {},
+        }
+        // The variables come last, once the whole request has been searched for
+        // sensitive uses of them
+        redactor.variables(&mut request);
+        request
+    }
+}
+
+/// Replaces sensitive values throughout a request
+struct Redactor<'a> {
+    redact_fields: &'a [&'a str],
+    /// Variables whose values are sensitive because of where they are used
+    sensitive_variables: BTreeSet<VariableName>,
+}
+
+impl Redactor<'_> {
+    fn matches(&self, name: &str) -> bool {
+        self.redact_fields.contains(&name)
+    }
+
+    fn redact(&self, name: &str, value: &mut serde_json::Value) {
+        if self.matches(name) {
+            *value = REDACTED.into();
+        } else {
+            self.value(value);
+        }
+    }
+
+    fn value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(object) => {
+                for (key, value) in object {
+                    self.redact(key, value);
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    self.value(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether values compared against `target` should be redacted
+    fn is_sensitive(&self, target: &ComparisonTarget) -> bool {
+        let (ComparisonTarget::Column {
+            name, field_path, ..
+        }
+        | ComparisonTarget::RootCollectionColumn { name, field_path }) = target;
+        self.matches(name.as_str())
+            || field_path
+                .iter()
+                .flatten()
+                .any(|field| self.matches(field.as_str()))
+    }
+
+    /// Mark `variable` as sensitive if it is given for the argument `name`
+    fn argument_variable(&mut self, name: &ArgumentName, variable: &VariableName) {
+        if self.matches(name.as_str()) {
+            self.sensitive_variables.insert(variable.clone());
+        }
+    }
+
+    fn variables(&self, request: &mut QueryRequest) {
+        for variables in request.variables.iter_mut().flatten() {
+            for (name, value) in variables {
+                if self.sensitive_variables.contains(name) {
+                    *value = REDACTED.into();
+                } else {
+                    self.redact(name.as_str(), value);
+                }
+            }
+        }
+    }
+}
+
+impl RequestVisitor for Redactor<'_> {
+    type Error = Infallible;
+
+    fn argument(&mut self, name: &ArgumentName, argument: &mut Argument) -> Result<(), Infallible> {
+        match argument {
+            Argument::Literal { value } => self.redact(name.as_str(), value),
+            Argument::Variable { name: variable } => self.argument_variable(name, variable),
+        }
+        Ok(())
+    }
+
+    fn relationship_argument(
+        &mut self,
+        name: &ArgumentName,
+        argument: &mut RelationshipArgument,
+    ) -> Result<(), Infallible> {
+        match argument {
+            RelationshipArgument::Literal { value } => self.redact(name.as_str(), value),
+            RelationshipArgument::Variable { name: variable } => {
+                self.argument_variable(name, variable);
+            }
+            RelationshipArgument::Column { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn comparison_value(
+        &mut self,
+        column: &ComparisonTarget,
+        value: &mut ComparisonValue,
+    ) -> Result<(), Infallible> {
+        let sensitive = self.is_sensitive(column);
+        match value {
+            ComparisonValue::Column { .. } => {}
+            ComparisonValue::Scalar { value } if sensitive => *value = REDACTED.into(),
+            ComparisonValue::Scalar { value } => self.value(value),
+            ComparisonValue::Variable { name } if sensitive => {
+                self.sensitive_variables.insert(name.clone());
+            }
+            ComparisonValue::Variable { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_redacted_variables() {
+        let request: QueryRequest = serde_json::from_value(serde_json::json!({
+            "collection": "users",
+            "arguments": { "api_key": { "type": "variable", "name": "key" } },
+            "query": {
+                "fields": { "name": { "type": "column", "column": "name" } },
+                "predicate": {
+                    "type": "and",
+                    "expressions": [
+                        {
+                            "type": "binary_comparison_operator",
+                            "column": { "type": "column", "name": "email", "path": [] },
+                            "operator": "eq",
+                            "value": { "type": "variable", "name": "address" }
+                        },
+                        {
+                            "type": "binary_comparison_operator",
+                            "column": { "type": "column", "name": "name", "path": [] },
+                            "operator": "eq",
+                            "value": { "type": "variable", "name": "search" }
+                        }
+                    ]
+                }
+            },
+            "collection_relationships": {},
+            "variables": [
+                { "key": "secret-1", "address": "alice@example.com", "search": "Alice" },
+                { "key": "secret-2", "address": "bob@example.com", "search": "Bob" }
+            ]
+        }))
+        .unwrap();
+
+        let redacted = serde_json::to_value(request.redacted(&["api_key", "email"])).unwrap();
+
+        assert_eq!(
+            redacted["variables"],
+            serde_json::json!([
+                { "key": "***", "address": "***", "search": "Alice" },
+                { "key": "***", "address": "***", "search": "Bob" }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_redacted() {
+        let request: QueryRequest = serde_json::from_value(serde_json::json!({
+            "collection": "users",
+            "arguments": {
+                "api_key": { "type": "literal", "value": "secret" },
+                "filter": {
+                    "type": "literal",
+                    "value": { "region": "eu", "credentials": [{ "password": "hunter2" }] }
+                }
+            },
+            "query": {
+                "fields": { "name": { "type": "column", "column": "name" } },
+                "predicate": {
+                    "type": "and",
+                    "expressions": [
+                        {
+                            "type": "binary_comparison_operator",
+                            "column": { "type": "column", "name": "email", "path": [] },
+                            "operator": "eq",
+                            "value": { "type": "scalar", "value": "alice@example.com" }
+                        },
+                        {
+                            "type": "binary_comparison_operator",
+                            "column": { "type": "column", "name": "name", "path": [] },
+                            "operator": "eq",
+                            "value": { "type": "scalar", "value": "Alice" }
+                        }
+                    ]
+                }
+            },
+            "collection_relationships": {},
+            "variables": [{ "api_key": "secret", "limit": 10 }]
+        }))
+        .unwrap();
+
+        let redacted =
+            serde_json::to_value(request.redacted(&["api_key", "password", "email"])).unwrap();
+
+        assert_eq!(redacted["arguments"]["api_key"]["value"], "***");
+        assert_eq!(
+            redacted["arguments"]["filter"]["value"],
+            serde_json::json!({ "region": "eu", "credentials": [{ "password": "***" }] })
+        );
+        let expressions = &redacted["query"]["predicate"]["expressions"];
+        assert_eq!(expressions[0]["value"]["value"], "***");
+        assert_eq!(expressions[1]["value"]["value"], "Alice");
+        assert_eq!(
+            redacted["variables"],
+            serde_json::json!([{ "api_key": "***", "limit": 10 }])
+        );
+
+        // The original is unchanged
+        assert_eq!(
+            request.arguments[&ArgumentName::from("api_key")],
+            Argument::literal("secret")
+        );
+    }
+}
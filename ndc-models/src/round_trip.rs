@@ -0,0 +1,378 @@
+//! Property-based round-trip tests, which serialize arbitrary requests and
+//! responses to JSON and back, to catch serde attributes (renames, tags, skipped
+//! fields) which would change or lose part of the wire format.
+
+use std::fmt::Debug;
+
+use proptest::collection::{btree_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+use serde::de::DeserializeOwned;
+
+use crate::*;
+
+fn name<T: From<String> + Debug>() -> impl Strategy<Value = T> {
+    "[a-z_][a-z0-9_]{0,7}".prop_map(T::from)
+}
+
+fn names<T: From<String> + Debug>() -> impl Strategy<Value = Option<Vec<T>>> {
+    option::of(vec(name(), 0..3))
+}
+
+fn index_map<V: Debug>(
+    value: impl Strategy<Value = V>,
+) -> impl Strategy<Value = IndexMap<FieldName, V>> {
+    vec((name(), value), 0..3).prop_map(|entries| entries.into_iter().collect())
+}
+
+/// Any JSON value except floating point numbers, which need not round-trip exactly
+fn json() -> impl Strategy<Value = serde_json::Value> {
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        any::<u64>().prop_map(serde_json::Value::from),
+        any::<String>().prop_map(serde_json::Value::from),
+    ];
+    leaf.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..4).prop_map(serde_json::Value::Array),
+            vec((any::<String>(), inner), 0..4)
+                .prop_map(|entries| serde_json::Value::Object(entries.into_iter().collect())),
+        ]
+    })
+}
+
+fn argument() -> impl Strategy<Value = Argument> {
+    prop_oneof![
+        name().prop_map(|name| Argument::Variable { name }),
+        json().prop_map(|value| Argument::Literal { value }),
+    ]
+}
+
+fn relationship_argument() -> impl Strategy<Value = RelationshipArgument> {
+    prop_oneof![
+        name().prop_map(|name| RelationshipArgument::Variable { name }),
+        json().prop_map(|value| RelationshipArgument::Literal { value }),
+        name().prop_map(|name| RelationshipArgument::Column { name }),
+    ]
+}
+
+fn relationship_arguments() -> impl Strategy<Value = BTreeMap<ArgumentName, RelationshipArgument>> {
+    btree_map(name(), relationship_argument(), 0..2)
+}
+
+fn relationship() -> impl Strategy<Value = Relationship> {
+    (
+        btree_map(name(), name(), 0..3),
+        prop_oneof![
+            Just(RelationshipType::Object),
+            Just(RelationshipType::Array)
+        ],
+        name(),
+        relationship_arguments(),
+    )
+        .prop_map(
+            |(column_mapping, relationship_type, target_collection, arguments)| Relationship {
+                column_mapping,
+                relationship_type,
+                target_collection,
+                arguments,
+            },
+        )
+}
+
+fn path(expression: BoxedStrategy<Expression>) -> impl Strategy<Value = Vec<PathElement>> {
+    let element = (
+        name(),
+        relationship_arguments(),
+        option::of(expression.prop_map(Box::new)),
+    )
+        .prop_map(|(relationship, arguments, predicate)| PathElement {
+            relationship,
+            arguments,
+            predicate,
+        });
+    vec(element, 0..2)
+}
+
+fn comparison_target(
+    expression: BoxedStrategy<Expression>,
+) -> impl Strategy<Value = ComparisonTarget> {
+    prop_oneof![
+        (name(), names(), path(expression)).prop_map(|(name, field_path, path)| {
+            ComparisonTarget::Column {
+                name,
+                field_path,
+                path,
+            }
+        }),
+        (name(), names()).prop_map(|(name, field_path)| {
+            ComparisonTarget::RootCollectionColumn { name, field_path }
+        }),
+    ]
+}
+
+fn expression() -> BoxedStrategy<Expression> {
+    let leaf = prop_oneof![
+        Just(Expression::And {
+            expressions: vec![]
+        }),
+        (name(), names()).prop_map(|(name, field_path)| Expression::UnaryComparisonOperator {
+            column: ComparisonTarget::RootCollectionColumn { name, field_path },
+            operator: UnaryComparisonOperator::IsNull,
+        }),
+    ];
+    leaf.prop_recursive(3, 12, 3, |inner| {
+        let value = prop_oneof![
+            comparison_target(inner.clone()).prop_map(|column| ComparisonValue::Column { column }),
+            json().prop_map(|value| ComparisonValue::Scalar { value }),
+            name().prop_map(|name| ComparisonValue::Variable { name }),
+        ];
+        let in_collection = prop_oneof![
+            (name(), relationship_arguments()).prop_map(|(relationship, arguments)| {
+                ExistsInCollection::Related {
+                    relationship,
+                    arguments,
+                }
+            }),
+            (name(), relationship_arguments()).prop_map(|(collection, arguments)| {
+                ExistsInCollection::Unrelated {
+                    collection,
+                    arguments,
+                }
+            }),
+        ];
+        prop_oneof![
+            vec(inner.clone(), 0..3).prop_map(|expressions| Expression::And { expressions }),
+            vec(inner.clone(), 0..3).prop_map(|expressions| Expression::Or { expressions }),
+            inner.clone().prop_map(|expression| Expression::Not {
+                expression: Box::new(expression)
+            }),
+            (comparison_target(inner.clone()), name(), value).prop_map(
+                |(column, operator, value)| Expression::BinaryComparisonOperator {
+                    column,
+                    operator,
+                    value,
+                }
+            ),
+            (in_collection, option::of(inner.prop_map(Box::new))).prop_map(
+                |(in_collection, predicate)| Expression::Exists {
+                    in_collection,
+                    predicate,
+                }
+            ),
+        ]
+    })
+    .boxed()
+}
+
+fn aggregate() -> impl Strategy<Value = Aggregate> {
+    prop_oneof![
+        (name(), names(), any::<bool>()).prop_map(|(column, field_path, distinct)| {
+            Aggregate::ColumnCount {
+                column,
+                field_path,
+                distinct,
+            }
+        }),
+        (name(), names(), name()).prop_map(|(column, field_path, function)| {
+            Aggregate::SingleColumn {
+                column,
+                field_path,
+                function,
+            }
+        }),
+        Just(Aggregate::StarCount {}),
+    ]
+}
+
+fn order_by() -> impl Strategy<Value = OrderBy> {
+    let target = prop_oneof![
+        (name(), names(), path(expression())).prop_map(|(name, field_path, path)| {
+            OrderByTarget::Column {
+                name,
+                field_path,
+                path,
+            }
+        }),
+        (name(), names(), name(), path(expression())).prop_map(
+            |(column, field_path, function, path)| OrderByTarget::SingleColumnAggregate {
+                column,
+                field_path,
+                function,
+                path,
+            }
+        ),
+        path(expression()).prop_map(|path| OrderByTarget::StarCountAggregate { path }),
+    ];
+    let element = (
+        prop_oneof![Just(OrderDirection::Asc), Just(OrderDirection::Desc)],
+        target,
+    )
+        .prop_map(|(order_direction, target)| OrderByElement {
+            order_direction,
+            target,
+        });
+    vec(element, 0..3).prop_map(|elements| OrderBy { elements })
+}
+
+/// Column fields, possibly selecting nested fields
+fn nested_field() -> BoxedStrategy<NestedField> {
+    let column = |fields: BoxedStrategy<Option<NestedField>>| {
+        (name(), fields, btree_map(name(), argument(), 0..2)).prop_map(
+            |(column, fields, arguments)| Field::Column {
+                column,
+                fields,
+                arguments,
+            },
+        )
+    };
+    let leaf = index_map(column(Just(None).boxed()))
+        .prop_map(|fields| NestedField::Object(NestedObject { fields }));
+    leaf.prop_recursive(3, 8, 2, move |inner| {
+        prop_oneof![
+            index_map(column(option::of(inner.clone()).boxed()))
+                .prop_map(|fields| NestedField::Object(NestedObject { fields })),
+            inner.prop_map(|fields| NestedField::Array(NestedArray {
+                fields: Box::new(fields)
+            })),
+        ]
+    })
+    .boxed()
+}
+
+fn query() -> impl Strategy<Value = Query> {
+    let query_with = |fields: BoxedStrategy<Option<IndexMap<FieldName, Field>>>| {
+        (
+            option::of(index_map(aggregate())),
+            fields,
+            option::of(any::<u32>()),
+            option::of(any::<u32>()),
+            option::of(order_by()),
+            option::of(expression()),
+        )
+            .prop_map(
+                |(aggregates, fields, limit, offset, order_by, predicate)| Query {
+                    aggregates,
+                    fields,
+                    limit,
+                    offset,
+                    order_by,
+                    predicate,
+                },
+            )
+    };
+    query_with(Just(None).boxed()).prop_recursive(2, 4, 2, move |inner| {
+        let field = prop_oneof![
+            (
+                name(),
+                option::of(nested_field()),
+                btree_map(name(), argument(), 0..2)
+            )
+                .prop_map(|(column, fields, arguments)| Field::Column {
+                    column,
+                    fields,
+                    arguments,
+                }),
+            (inner, name(), relationship_arguments()).prop_map(
+                |(query, relationship, arguments)| Field::Relationship {
+                    query: Box::new(query),
+                    relationship,
+                    arguments,
+                }
+            ),
+        ];
+        query_with(option::of(index_map(field)).boxed())
+    })
+}
+
+fn query_request() -> impl Strategy<Value = QueryRequest> {
+    (
+        name(),
+        query(),
+        btree_map(name(), argument(), 0..3),
+        btree_map(name(), relationship(), 0..2),
+        option::of(vec(btree_map(name(), json(), 0..3), 0..3)),
+    )
+        .prop_map(
+            |(collection, query, arguments, collection_relationships, variables)| QueryRequest {
+                collection,
+                query,
+                arguments,
+                collection_relationships,
+                variables,
+            },
+        )
+}
+
+fn query_response() -> impl Strategy<Value = QueryResponse> {
+    let row_set = (
+        option::of(index_map(json())),
+        option::of(vec(index_map(json().prop_map(RowFieldValue)), 0..3)),
+    )
+        .prop_map(|(aggregates, rows)| RowSet { aggregates, rows });
+    vec(row_set, 0..3).prop_map(QueryResponse)
+}
+
+fn mutation_request() -> impl Strategy<Value = MutationRequest> {
+    let operation = (
+        name(),
+        btree_map(name(), json(), 0..3),
+        option::of(nested_field()),
+    )
+        .prop_map(|(name, arguments, fields)| MutationOperation::Procedure {
+            name,
+            arguments,
+            fields,
+        });
+    (
+        vec(operation, 0..3),
+        btree_map(name(), relationship(), 0..2),
+    )
+        .prop_map(|(operations, collection_relationships)| MutationRequest {
+            operations,
+            collection_relationships,
+        })
+}
+
+fn mutation_response() -> impl Strategy<Value = MutationResponse> {
+    vec(
+        json().prop_map(|result| MutationOperationResults::Procedure { result }),
+        0..3,
+    )
+    .prop_map(|operation_results| MutationResponse { operation_results })
+}
+
+fn round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let json = serde_json::to_string(value).map_err(|e| TestCaseError::fail(e.to_string()))?;
+    let parsed: T =
+        serde_json::from_str(&json).map_err(|e| TestCaseError::fail(format!("{e} in {json}")))?;
+    prop_assert_eq!(&parsed, value, "serialized as {}", json);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_query_request_round_trip(request in query_request()) {
+        round_trip(&request)?;
+    }
+
+    #[test]
+    fn test_query_response_round_trip(response in query_response()) {
+        round_trip(&response)?;
+    }
+
+    #[test]
+    fn test_mutation_request_round_trip(request in mutation_request()) {
+        round_trip(&request)?;
+    }
+
+    #[test]
+    fn test_mutation_response_round_trip(response in mutation_response()) {
+        round_trip(&response)?;
+    }
+}
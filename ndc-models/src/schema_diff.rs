@@ -0,0 +1,376 @@
+use std::fmt;
+
+use crate::*;
+
+/// The differences between two [`SchemaResponse`]s, for example before and after
+/// upgrading a connector, split by whether requests written against the old schema
+/// remain valid
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    /// Changes which existing requests are unaffected by, such as new collections
+    pub additive: Vec<SchemaChange>,
+    /// Changes which may cause existing requests to fail, such as removed columns
+    pub breaking: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.additive.is_empty() && self.breaking.is_empty()
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let mut lines = vec![];
+        for (heading, changes) in [("breaking", &self.breaking), ("additive", &self.additive)] {
+            if !changes.is_empty() {
+                lines.push(format!("{heading} changes:"));
+                lines.extend(changes.iter().map(ToString::to_string));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// A change to the item at `path`, a dotted path into the schema such as
+/// `object_types.article.fields.title`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub path: String,
+    pub kind: SchemaChangeKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaChangeKind {
+    Added,
+    Removed,
+    /// The type of a field or argument, or the result type of a function, procedure
+    /// or aggregate function, changed
+    TypeChanged {
+        old: Type,
+        new: Type,
+    },
+    /// Any other change to the item's definition
+    Changed,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = &self.path;
+        match &self.kind {
+            SchemaChangeKind::Added => write!(f, "+ {path}"),
+            SchemaChangeKind::Removed => write!(f, "- {path}"),
+            SchemaChangeKind::TypeChanged { old, new } => {
+                write!(f, "~ {path}: {} -> {}", TypeSyntax(old), TypeSyntax(new))
+            }
+            SchemaChangeKind::Changed => write!(f, "~ {path}"),
+        }
+    }
+}
+
+/// Writes a type compactly, for example `[Int?]` for an array of nullable `Int`s
+struct TypeSyntax<'a>(&'a Type);
+
+impl fmt::Display for TypeSyntax<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Type::Named { name } => write!(f, "{name}"),
+            Type::Nullable { underlying_type } => write!(f, "{}?", TypeSyntax(underlying_type)),
+            Type::Array { element_type } => write!(f, "[{}]", TypeSyntax(element_type)),
+            Type::Predicate { object_type_name } => write!(f, "predicate<{object_type_name}>"),
+        }
+    }
+}
+
+/// Compare two versions of a connector's schema.
+///
+/// Removing anything, or changing its type, is breaking. Adding anything is
+/// additive, except for arguments which are not nullable, which existing requests
+/// do not provide. Fields added to object types are always additive, even though
+/// the object type may be used as an argument type. Descriptions are ignored.
+pub fn schema_diff(old: &SchemaResponse, new: &SchemaResponse) -> SchemaDiff {
+    let mut differ = Differ::default();
+    differ.entries(
+        "scalar_types",
+        &old.scalar_types,
+        &new.scalar_types,
+        |_| false,
+        Differ::scalar_type,
+    );
+    differ.entries(
+        "object_types",
+        &old.object_types,
+        &new.object_types,
+        |_| false,
+        Differ::object_type,
+    );
+    differ.entries(
+        "collections",
+        old.collections.iter().map(|info| (&info.name, info)),
+        new.collections.iter().map(|info| (&info.name, info)),
+        |_| false,
+        Differ::collection,
+    );
+    differ.entries(
+        "functions",
+        old.functions.iter().map(|info| (&info.name, info)),
+        new.functions.iter().map(|info| (&info.name, info)),
+        |_| false,
+        |differ, path, old, new| {
+            differ.arguments(path, &old.arguments, &new.arguments);
+            differ.type_changed(
+                &format!("{path}.result_type"),
+                &old.result_type,
+                &new.result_type,
+            );
+        },
+    );
+    differ.entries(
+        "procedures",
+        old.procedures.iter().map(|info| (&info.name, info)),
+        new.procedures.iter().map(|info| (&info.name, info)),
+        |_| false,
+        |differ, path, old, new| {
+            differ.arguments(path, &old.arguments, &new.arguments);
+            differ.type_changed(
+                &format!("{path}.result_type"),
+                &old.result_type,
+                &new.result_type,
+            );
+        },
+    );
+    differ.diff
+}
+
+#[derive(Default)]
+struct Differ {
+    diff: SchemaDiff,
+}
+
+impl Differ {
+    fn change(&mut self, breaking: bool, path: String, kind: SchemaChangeKind) {
+        let changes = if breaking {
+            &mut self.diff.breaking
+        } else {
+            &mut self.diff.additive
+        };
+        changes.push(SchemaChange { path, kind });
+    }
+
+    /// Compare two collections of named items. Removed items are breaking, added
+    /// items are breaking if `added_is_breaking` says so, and items present in both
+    /// are compared with `compare`.
+    fn entries<'a, V: 'a>(
+        &mut self,
+        path: &str,
+        old: impl IntoIterator<Item = (impl fmt::Display, &'a V)>,
+        new: impl IntoIterator<Item = (impl fmt::Display, &'a V)>,
+        added_is_breaking: fn(&V) -> bool,
+        compare: impl Fn(&mut Self, &str, &V, &V),
+    ) {
+        let old: BTreeMap<String, &V> = old
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        let new: BTreeMap<String, &V> = new
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+
+        for (name, &old_value) in &old {
+            let path = format!("{path}.{name}");
+            match new.get(name) {
+                Some(&new_value) => compare(self, &path, old_value, new_value),
+                None => self.change(true, path, SchemaChangeKind::Removed),
+            }
+        }
+        for (name, &new_value) in &new {
+            if !old.contains_key(name) {
+                let breaking = added_is_breaking(new_value);
+                self.change(breaking, format!("{path}.{name}"), SchemaChangeKind::Added);
+            }
+        }
+    }
+
+    fn changed<V: PartialEq>(&mut self, path: &str, old: &V, new: &V) {
+        if old != new {
+            self.change(true, path.into(), SchemaChangeKind::Changed);
+        }
+    }
+
+    fn type_changed(&mut self, path: &str, old: &Type, new: &Type) {
+        if old != new {
+            self.change(
+                true,
+                path.into(),
+                SchemaChangeKind::TypeChanged {
+                    old: old.clone(),
+                    new: new.clone(),
+                },
+            );
+        }
+    }
+
+    fn scalar_type(&mut self, path: &str, old: &ScalarType, new: &ScalarType) {
+        self.changed(
+            &format!("{path}.representation"),
+            &old.representation,
+            &new.representation,
+        );
+        self.entries(
+            &format!("{path}.aggregate_functions"),
+            &old.aggregate_functions,
+            &new.aggregate_functions,
+            |_| false,
+            |differ, path, old, new| {
+                differ.type_changed(
+                    &format!("{path}.result_type"),
+                    &old.result_type,
+                    &new.result_type,
+                );
+            },
+        );
+        self.entries(
+            &format!("{path}.comparison_operators"),
+            &old.comparison_operators,
+            &new.comparison_operators,
+            |_| false,
+            Differ::changed,
+        );
+    }
+
+    fn object_type(&mut self, path: &str, old: &ObjectType, new: &ObjectType) {
+        self.entries(
+            &format!("{path}.fields"),
+            &old.fields,
+            &new.fields,
+            |_| false,
+            |differ, path, old, new| {
+                differ.type_changed(&format!("{path}.type"), &old.r#type, &new.r#type);
+                differ.arguments(path, &old.arguments, &new.arguments);
+            },
+        );
+    }
+
+    fn collection(&mut self, path: &str, old: &CollectionInfo, new: &CollectionInfo) {
+        self.changed(
+            &format!("{path}.type"),
+            &old.collection_type,
+            &new.collection_type,
+        );
+        self.arguments(path, &old.arguments, &new.arguments);
+        self.entries(
+            &format!("{path}.uniqueness_constraints"),
+            &old.uniqueness_constraints,
+            &new.uniqueness_constraints,
+            |_| false,
+            Differ::changed,
+        );
+        self.entries(
+            &format!("{path}.foreign_keys"),
+            &old.foreign_keys,
+            &new.foreign_keys,
+            |_| false,
+            Differ::changed,
+        );
+    }
+
+    /// Compare the arguments of the item at `path`
+    fn arguments(
+        &mut self,
+        path: &str,
+        old: &BTreeMap<ArgumentName, ArgumentInfo>,
+        new: &BTreeMap<ArgumentName, ArgumentInfo>,
+    ) {
+        self.entries(
+            &format!("{path}.arguments"),
+            old,
+            new,
+            |argument| !matches!(argument.argument_type, Type::Nullable { .. }),
+            |differ, path, old, new| {
+                differ.type_changed(
+                    &format!("{path}.type"),
+                    &old.argument_type,
+                    &new.argument_type,
+                );
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::*;
+
+    fn schema() -> SchemaResponse {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../ndc-reference/tests/schema/expected.json");
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn named(name: &str) -> Type {
+        Type::Named { name: name.into() }
+    }
+
+    fn argument(argument_type: Type) -> ArgumentInfo {
+        ArgumentInfo {
+            description: None,
+            argument_type,
+        }
+    }
+
+    #[test]
+    fn test_schema_diff() {
+        let old = schema();
+        let mut new = old.clone();
+        assert!(schema_diff(&old, &new).is_empty());
+
+        new.collections
+            .retain(|collection| collection.name.as_str() != "institutions");
+        let article = new.object_types.get_mut("article").unwrap();
+        article.fields.get_mut("id").unwrap().r#type = named("String");
+        article.fields.insert(
+            "published".into(),
+            ObjectField {
+                description: None,
+                r#type: Type::Nullable {
+                    underlying_type: Box::new(named("Int")),
+                },
+                arguments: BTreeMap::new(),
+            },
+        );
+        let function = &mut new.functions[0];
+        function.arguments.insert(
+            "since".into(),
+            argument(Type::Nullable {
+                underlying_type: Box::new(named("Int")),
+            }),
+        );
+        function
+            .arguments
+            .insert("author_id".into(), argument(named("Int")));
+
+        let diff = schema_diff(&old, &new);
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.to_string(),
+            "breaking changes:\n\
+             ~ object_types.article.fields.id.type: Int -> String\n\
+             - collections.institutions\n\
+             + functions.latest_article_id.arguments.author_id\n\
+             additive changes:\n\
+             + object_types.article.fields.published\n\
+             + functions.latest_article_id.arguments.since"
+        );
+    }
+}
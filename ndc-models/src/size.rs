@@ -0,0 +1,60 @@
+use std::io;
+
+use crate::*;
+
+impl QueryRequest {
+    /// The size in bytes of the request serialized as compact JSON, computed without
+    /// allocating the serialized body
+    pub fn estimated_body_size(&self) -> usize {
+        serialized_size(self)
+    }
+}
+
+impl MutationRequest {
+    /// The size in bytes of the request serialized as compact JSON, computed without
+    /// allocating the serialized body
+    pub fn estimated_body_size(&self) -> usize {
+        serialized_size(self)
+    }
+}
+
+fn serialized_size<T: Serialize>(value: &T) -> usize {
+    let mut counter = ByteCounter(0);
+    // Requests only contain maps with string keys, so serialization can't fail
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_estimated_body_size() {
+        let request = MutationRequest {
+            operations: vec![MutationOperation::with_args(
+                "upsert_article",
+                serde_json::json!({ "title": "Über" }),
+            )
+            .unwrap()],
+            collection_relationships: BTreeMap::new(),
+        };
+        assert_eq!(
+            request.estimated_body_size(),
+            serde_json::to_vec(&request).unwrap().len()
+        );
+    }
+}
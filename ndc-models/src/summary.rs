@@ -0,0 +1,119 @@
+use std::fmt;
+
+use crate::*;
+
+impl QueryRequest {
+    /// A one-line description of the request, for logs and support tickets, for
+    /// example `query articles: fields[id,title], filter: present, limit: 10, vars: 3`.
+    /// Only the parts of the query which are present are described.
+    pub fn summary(&self) -> String {
+        Summary(self).to_string()
+    }
+}
+
+struct Summary<'a>(&'a QueryRequest);
+
+impl fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let request = self.0;
+        let query = &request.query;
+        write!(f, "query {}", request.collection)?;
+
+        let mut separator = ": ";
+        let mut part = |f: &mut fmt::Formatter<'_>, args: fmt::Arguments<'_>| {
+            f.write_str(separator)?;
+            separator = ", ";
+            f.write_fmt(args)
+        };
+
+        if let Some(fields) = &query.fields {
+            part(f, format_args!("fields[{}]", Names(fields.keys())))?;
+        }
+        if let Some(aggregates) = &query.aggregates {
+            part(f, format_args!("aggregates[{}]", Names(aggregates.keys())))?;
+        }
+        if query.predicate.is_some() {
+            part(f, format_args!("filter: present"))?;
+        }
+        if let Some(order_by) = &query.order_by {
+            part(f, format_args!("order_by: {}", order_by.elements.len()))?;
+        }
+        if let Some(limit) = query.limit {
+            part(f, format_args!("limit: {limit}"))?;
+        }
+        if let Some(offset) = query.offset {
+            part(f, format_args!("offset: {offset}"))?;
+        }
+        if !request.arguments.is_empty() {
+            part(f, format_args!("args: {}", request.arguments.len()))?;
+        }
+        if let Some(variables) = &request.variables {
+            part(f, format_args!("vars: {}", variables.len()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes names separated by commas, without collecting them first
+struct Names<I>(I);
+
+impl<'a, I> fmt::Display for Names<I>
+where
+    I: Iterator<Item = &'a FieldName> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, name) in self.0.clone().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_summary() {
+        let column = |name: &str| Field::Column {
+            column: name.into(),
+            fields: None,
+            arguments: BTreeMap::new(),
+        };
+        let mut request = QueryRequest {
+            collection: "articles".into(),
+            query: Query {
+                aggregates: None,
+                fields: Some(IndexMap::from([
+                    ("id".into(), column("id")),
+                    ("title".into(), column("title")),
+                ])),
+                limit: Some(10),
+                offset: None,
+                order_by: None,
+                predicate: None,
+            },
+            arguments: BTreeMap::new(),
+            collection_relationships: BTreeMap::new(),
+            variables: Some(vec![BTreeMap::new(); 3]),
+        };
+        assert_eq!(
+            request.summary(),
+            "query articles: fields[id,title], limit: 10, vars: 3"
+        );
+
+        request.query = Query {
+            aggregates: None,
+            fields: None,
+            limit: None,
+            offset: None,
+            order_by: None,
+            predicate: None,
+        };
+        request.variables = None;
+        assert_eq!(request.summary(), "query articles");
+    }
+}
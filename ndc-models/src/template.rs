@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+
+use serde_json::Value;
+
+use crate::*;
+
+/// An error encountered while building a request from a template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The template is not valid JSON
+    InvalidJson { message: String },
+    /// The template refers to a variable which was not provided
+    UnknownPlaceholder { name: String },
+    /// A variable used within a larger string is not a string, number or boolean
+    InvalidSubstitution { name: String },
+    /// The template does not describe a valid request once substituted
+    InvalidRequest { message: String },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::InvalidJson { message } => {
+                write!(f, "template is not valid JSON: {message}")
+            }
+            TemplateError::UnknownPlaceholder { name } => {
+                write!(f, "template refers to unknown variable {name}")
+            }
+            TemplateError::InvalidSubstitution { name } => {
+                write!(f, "variable {name} cannot be substituted into a string")
+            }
+            TemplateError::InvalidRequest { message } => {
+                write!(f, "template is not a valid request: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl QueryRequest {
+    /// Build a request from a JSON template containing `${name}` placeholders.
+    ///
+    /// A string which consists of a single placeholder is replaced by the variable's
+    /// value, whatever its type. Placeholders within a larger string are replaced by
+    /// the variable's value formatted as text.
+    pub fn from_template<S: BuildHasher>(
+        template: &str,
+        vars: &HashMap<String, Value, S>,
+    ) -> Result<QueryRequest, TemplateError> {
+        let template: Value =
+            serde_json::from_str(template).map_err(|err| TemplateError::InvalidJson {
+                message: err.to_string(),
+            })?;
+        let request = substitute(template, vars)?;
+        serde_json::from_value(request).map_err(|err| TemplateError::InvalidRequest {
+            message: err.to_string(),
+        })
+    }
+}
+
+fn substitute<S: BuildHasher>(
+    value: Value,
+    vars: &HashMap<String, Value, S>,
+) -> Result<Value, TemplateError> {
+    match value {
+        Value::String(string) => match whole_placeholder(&string) {
+            Some(name) => lookup(name, vars).cloned(),
+            None => Ok(Value::String(interpolate(&string, vars)?)),
+        },
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| substitute(value, vars))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .into_iter()
+            .map(|(key, value)| Ok((interpolate(&key, vars)?, substitute(value, vars)?)))
+            .collect::<Result<_, _>>()
+            .map(Value::Object),
+        Value::Null | Value::Bool(_) | Value::Number(_) => Ok(value),
+    }
+}
+
+/// The variable name, if `string` consists of exactly one placeholder
+fn whole_placeholder(string: &str) -> Option<&str> {
+    let name = string.strip_prefix("${")?.strip_suffix('}')?;
+    (!name.contains('}')).then_some(name)
+}
+
+fn interpolate<S: BuildHasher>(
+    string: &str,
+    vars: &HashMap<String, Value, S>,
+) -> Result<String, TemplateError> {
+    let mut result = String::new();
+    let mut rest = string;
+
+    while let Some(start) = rest.find("${") {
+        let Some(length) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + length];
+        result.push_str(&rest[..start]);
+        match lookup(name, vars)? {
+            Value::String(value) => result.push_str(value),
+            Value::Number(value) => result.push_str(&value.to_string()),
+            Value::Bool(value) => result.push_str(&value.to_string()),
+            Value::Null | Value::Array(_) | Value::Object(_) => {
+                return Err(TemplateError::InvalidSubstitution { name: name.into() })
+            }
+        }
+        rest = &rest[start + 2 + length + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn lookup<'a, S: BuildHasher>(
+    name: &str,
+    vars: &'a HashMap<String, Value, S>,
+) -> Result<&'a Value, TemplateError> {
+    vars.get(name)
+        .ok_or_else(|| TemplateError::UnknownPlaceholder { name: name.into() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use crate::*;
+
+    const TEMPLATE: &str = r#"{
+        "collection": "${collection}",
+        "query": {
+            "fields": { "${field}_value": { "type": "column", "column": "${field}" } },
+            "limit": "${limit}"
+        },
+        "arguments": {},
+        "collection_relationships": {}
+    }"#;
+
+    #[test]
+    fn test_from_template() {
+        let vars = HashMap::from([
+            ("collection".to_owned(), json!("articles")),
+            ("field".to_owned(), json!("title")),
+            ("limit".to_owned(), json!(10)),
+        ]);
+
+        let request = QueryRequest::from_template(TEMPLATE, &vars).unwrap();
+        assert_eq!(request.collection, CollectionName::from("articles"));
+        assert_eq!(request.query.limit, Some(10));
+        assert!(request
+            .query
+            .fields
+            .unwrap()
+            .contains_key(&FieldName::from("title_value")));
+    }
+
+    #[test]
+    fn test_from_template_errors() {
+        let vars = HashMap::from([
+            ("collection".to_owned(), json!("articles")),
+            ("field".to_owned(), json!(["title"])),
+        ]);
+
+        assert_eq!(
+            QueryRequest::from_template(TEMPLATE, &vars),
+            Err(TemplateError::InvalidSubstitution {
+                name: "field".into()
+            })
+        );
+
+        let vars = HashMap::from([("collection".to_owned(), json!("articles"))]);
+        assert!(matches!(
+            QueryRequest::from_template(TEMPLATE, &vars),
+            Err(TemplateError::UnknownPlaceholder { .. })
+        ));
+
+        let vars = HashMap::from([
+            ("collection".to_owned(), json!("articles")),
+            ("field".to_owned(), json!("title")),
+            ("limit".to_owned(), json!("ten")),
+        ]);
+        assert!(matches!(
+            QueryRequest::from_template(TEMPLATE, &vars),
+            Err(TemplateError::InvalidRequest { .. })
+        ));
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::*;
+
+/// A problem found while checking a [`SchemaResponse`] for internal consistency
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaError {
+    /// A scalar type and an object type were declared with the same name
+    OverlappingTypeName { name: TypeName },
+    /// A scalar type declares more than one equality operator
+    MultipleEqualityOperators { scalar_type: ScalarTypeName },
+    /// A named type is neither a declared scalar type nor a declared object type
+    NamedTypeIsNotDefined { location: String, name: TypeName },
+    /// A predicate type refers to an object type which is not declared
+    ObjectTypeIsNotDefined {
+        location: String,
+        name: ObjectTypeName,
+    },
+    /// More than one collection or function was declared with the same name
+    DuplicateCollectionName { name: CollectionName },
+    /// A collection's object type is not declared
+    CollectionTypeIsNotDefined {
+        collection: CollectionName,
+        collection_type: ObjectTypeName,
+    },
+    /// A uniqueness or foreign key constraint refers to a column which is not
+    /// defined on the object type of the collection it references
+    ColumnIsNotDefined { location: String, column: FieldName },
+    /// A foreign key refers to a collection which is not declared
+    ForeignCollectionIsNotDefined {
+        collection: CollectionName,
+        foreign_key: String,
+        foreign_collection: CollectionName,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::OverlappingTypeName { name } => {
+                write!(f, "type {name} is declared as both a scalar and an object type")
+            }
+            SchemaError::MultipleEqualityOperators { scalar_type } => {
+                write!(f, "scalar type {scalar_type} has multiple equality operators")
+            }
+            SchemaError::NamedTypeIsNotDefined { location, name } => {
+                write!(
+                    f,
+                    "{location}: named type {name} is not a defined object or scalar type"
+                )
+            }
+            SchemaError::ObjectTypeIsNotDefined { location, name } => {
+                write!(f, "{location}: object type {name} is not defined")
+            }
+            SchemaError::DuplicateCollectionName { name } => {
+                write!(f, "collection {name} is declared more than once")
+            }
+            SchemaError::CollectionTypeIsNotDefined {
+                collection,
+                collection_type,
+            } => write!(
+                f,
+                "collection {collection}: collection type {collection_type} is not a defined object type"
+            ),
+            SchemaError::ColumnIsNotDefined { location, column } => {
+                write!(f, "{location}: column {column} is not defined")
+            }
+            SchemaError::ForeignCollectionIsNotDefined {
+                collection,
+                foreign_key,
+                foreign_collection,
+            } => write!(
+                f,
+                "collection {collection}: foreign key {foreign_key} refers to undefined collection {foreign_collection}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Check a [`SchemaResponse`] for internal consistency.
+///
+/// Every type referenced by a scalar type, object type, collection, function or
+/// procedure must be declared, and every constraint must refer to known collections
+/// and columns. All problems are collected, rather than stopping at the first one.
+pub fn validate_schema(schema: &SchemaResponse) -> Result<(), Vec<SchemaError>> {
+    let mut errors = vec![];
+
+    for (type_name, scalar_type) in &schema.scalar_types {
+        if schema.object_types.contains_key(type_name.as_str()) {
+            errors.push(SchemaError::OverlappingTypeName {
+                name: type_name.inner().clone(),
+            });
+        }
+
+        for (function_name, aggregate_function) in &scalar_type.aggregate_functions {
+            validate_type(
+                schema,
+                &aggregate_function.result_type,
+                &format!("scalar type {type_name}, aggregate function {function_name}"),
+                &mut errors,
+            );
+        }
+
+        let mut has_equality = false;
+
+        for (operator_name, comparison_operator) in &scalar_type.comparison_operators {
+            match comparison_operator {
+                ComparisonOperatorDefinition::Equal => {
+                    if has_equality {
+                        errors.push(SchemaError::MultipleEqualityOperators {
+                            scalar_type: type_name.clone(),
+                        });
+                    }
+                    has_equality = true;
+                }
+                ComparisonOperatorDefinition::In => {}
+                ComparisonOperatorDefinition::Custom { argument_type } => validate_type(
+                    schema,
+                    argument_type,
+                    &format!("scalar type {type_name}, comparison operator {operator_name}"),
+                    &mut errors,
+                ),
+            }
+        }
+    }
+
+    for (type_name, object_type) in &schema.object_types {
+        for (field_name, object_field) in &object_type.fields {
+            let location = format!("object type {type_name}, field {field_name}");
+            validate_type(schema, &object_field.r#type, &location, &mut errors);
+            validate_arguments(schema, &object_field.arguments, &location, &mut errors);
+        }
+    }
+
+    let mut collection_names = BTreeSet::new();
+
+    for collection_info in &schema.collections {
+        if !collection_names.insert(collection_info.name.clone()) {
+            errors.push(SchemaError::DuplicateCollectionName {
+                name: collection_info.name.clone(),
+            });
+        }
+
+        validate_arguments(
+            schema,
+            &collection_info.arguments,
+            &format!("collection {}", collection_info.name),
+            &mut errors,
+        );
+
+        let Some(object_type) = schema.object_types.get(&collection_info.collection_type) else {
+            errors.push(SchemaError::CollectionTypeIsNotDefined {
+                collection: collection_info.name.clone(),
+                collection_type: collection_info.collection_type.clone(),
+            });
+            continue;
+        };
+
+        for (constraint_name, constraint) in &collection_info.uniqueness_constraints {
+            for column in &constraint.unique_columns {
+                if !object_type.fields.contains_key(column) {
+                    errors.push(SchemaError::ColumnIsNotDefined {
+                        location: format!(
+                            "collection {}, uniqueness constraint {constraint_name}",
+                            collection_info.name
+                        ),
+                        column: column.clone(),
+                    });
+                }
+            }
+        }
+
+        for (foreign_key_name, foreign_key) in &collection_info.foreign_keys {
+            let location = format!(
+                "collection {}, foreign key {foreign_key_name}",
+                collection_info.name
+            );
+
+            for column in foreign_key.column_mapping.keys() {
+                if !object_type.fields.contains_key(column) {
+                    errors.push(SchemaError::ColumnIsNotDefined {
+                        location: location.clone(),
+                        column: column.clone(),
+                    });
+                }
+            }
+
+            let Some(foreign_collection) = schema
+                .collections
+                .iter()
+                .find(|c| c.name == foreign_key.foreign_collection)
+            else {
+                errors.push(SchemaError::ForeignCollectionIsNotDefined {
+                    collection: collection_info.name.clone(),
+                    foreign_key: foreign_key_name.clone(),
+                    foreign_collection: foreign_key.foreign_collection.clone(),
+                });
+                continue;
+            };
+
+            // A missing collection type is reported against the foreign collection itself
+            if let Some(foreign_object_type) =
+                schema.object_types.get(&foreign_collection.collection_type)
+            {
+                for column in foreign_key.column_mapping.values() {
+                    if !foreign_object_type.fields.contains_key(column) {
+                        errors.push(SchemaError::ColumnIsNotDefined {
+                            location: location.clone(),
+                            column: column.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    for function_info in &schema.functions {
+        if !collection_names.insert(function_info.name.inner().clone()) {
+            errors.push(SchemaError::DuplicateCollectionName {
+                name: function_info.name.inner().clone(),
+            });
+        }
+
+        let location = format!("function {}", function_info.name);
+        validate_type(
+            schema,
+            &function_info.result_type,
+            &format!("{location}, result type"),
+            &mut errors,
+        );
+        validate_arguments(schema, &function_info.arguments, &location, &mut errors);
+    }
+
+    for procedure_info in &schema.procedures {
+        let location = format!("procedure {}", procedure_info.name);
+        validate_type(
+            schema,
+            &procedure_info.result_type,
+            &format!("{location}, result type"),
+            &mut errors,
+        );
+        validate_arguments(schema, &procedure_info.arguments, &location, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_arguments(
+    schema: &SchemaResponse,
+    arguments: &BTreeMap<ArgumentName, ArgumentInfo>,
+    location: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    for (argument_name, argument_info) in arguments {
+        validate_type(
+            schema,
+            &argument_info.argument_type,
+            &format!("{location}, argument {argument_name}"),
+            errors,
+        );
+    }
+}
+
+fn validate_type(
+    schema: &SchemaResponse,
+    r#type: &Type,
+    location: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    match r#type {
+        Type::Named { name } => {
+            if !schema.object_types.contains_key(name.as_str())
+                && !schema.scalar_types.contains_key(name.as_str())
+            {
+                errors.push(SchemaError::NamedTypeIsNotDefined {
+                    location: location.into(),
+                    name: name.clone(),
+                });
+            }
+        }
+        Type::Nullable { underlying_type } => {
+            validate_type(schema, underlying_type, location, errors);
+        }
+        Type::Array { element_type } => {
+            validate_type(schema, element_type, location, errors);
+        }
+        Type::Predicate { object_type_name } => {
+            if !schema.object_types.contains_key(object_type_name) {
+                errors.push(SchemaError::ObjectTypeIsNotDefined {
+                    location: location.into(),
+                    name: object_type_name.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> Type {
+        Type::Named { name: name.into() }
+    }
+
+    fn schema() -> SchemaResponse {
+        SchemaResponse {
+            scalar_types: BTreeMap::from_iter([(
+                "Int".into(),
+                ScalarType {
+                    representation: None,
+                    aggregate_functions: BTreeMap::new(),
+                    comparison_operators: BTreeMap::from_iter([(
+                        "eq".into(),
+                        ComparisonOperatorDefinition::Equal,
+                    )]),
+                },
+            )]),
+            object_types: BTreeMap::from_iter([
+                (
+                    "author".into(),
+                    ObjectType {
+                        description: None,
+                        fields: BTreeMap::from_iter([(
+                            "id".into(),
+                            ObjectField {
+                                description: None,
+                                r#type: named("Int"),
+                                arguments: BTreeMap::new(),
+                            },
+                        )]),
+                    },
+                ),
+                (
+                    "article".into(),
+                    ObjectType {
+                        description: None,
+                        fields: BTreeMap::from_iter([(
+                            "author_id".into(),
+                            ObjectField {
+                                description: None,
+                                r#type: named("Int"),
+                                arguments: BTreeMap::new(),
+                            },
+                        )]),
+                    },
+                ),
+            ]),
+            collections: vec![
+                CollectionInfo {
+                    name: "authors".into(),
+                    description: None,
+                    arguments: BTreeMap::new(),
+                    collection_type: "author".into(),
+                    uniqueness_constraints: BTreeMap::from_iter([(
+                        "AuthorByID".into(),
+                        UniquenessConstraint {
+                            unique_columns: vec!["id".into()],
+                        },
+                    )]),
+                    foreign_keys: BTreeMap::new(),
+                },
+                CollectionInfo {
+                    name: "articles".into(),
+                    description: None,
+                    arguments: BTreeMap::new(),
+                    collection_type: "article".into(),
+                    uniqueness_constraints: BTreeMap::new(),
+                    foreign_keys: BTreeMap::from_iter([(
+                        "Article_AuthorID".into(),
+                        ForeignKeyConstraint {
+                            column_mapping: BTreeMap::from_iter([(
+                                "author_id".into(),
+                                "id".into(),
+                            )]),
+                            foreign_collection: "authors".into(),
+                        },
+                    )]),
+                },
+            ],
+            functions: vec![],
+            procedures: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        assert_eq!(validate_schema(&schema()), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_schema_reports_all_errors() {
+        let mut schema = schema();
+        schema.collections[0].collection_type = "writer".into();
+        schema.collections[1].foreign_keys.insert(
+            "Article_EditorID".into(),
+            ForeignKeyConstraint {
+                column_mapping: BTreeMap::from_iter([("editor_id".into(), "id".into())]),
+                foreign_collection: "editors".into(),
+            },
+        );
+        schema.functions.push(FunctionInfo {
+            name: "latest_article".into(),
+            description: None,
+            arguments: BTreeMap::new(),
+            result_type: named("String"),
+        });
+
+        assert_eq!(
+            validate_schema(&schema),
+            Err(vec![
+                SchemaError::CollectionTypeIsNotDefined {
+                    collection: "authors".into(),
+                    collection_type: "writer".into(),
+                },
+                SchemaError::ColumnIsNotDefined {
+                    location: "collection articles, foreign key Article_EditorID".into(),
+                    column: "editor_id".into(),
+                },
+                SchemaError::ForeignCollectionIsNotDefined {
+                    collection: "articles".into(),
+                    foreign_key: "Article_EditorID".into(),
+                    foreign_collection: "editors".into(),
+                },
+                SchemaError::NamedTypeIsNotDefined {
+                    location: "function latest_article, result type".into(),
+                    name: "String".into(),
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+use crate::*;
+
+/// An error encountered while reading an aggregate from a [`RowSet`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AggregateError {
+    /// The aggregate was not returned in the row set
+    Missing { key: FieldName },
+    /// The aggregate could not be decoded as the requested type
+    InvalidValue { key: FieldName, message: String },
+}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateError::Missing { key } => write!(f, "aggregate {key} is missing"),
+            AggregateError::InvalidValue { key, message } => {
+                write!(f, "aggregate {key} has an invalid value: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AggregateError {}
+
+/// An error encountered while decoding a [`RowFieldValue`] as a specific type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldTypeError {
+    /// The JSON type of the value with its article, for example `a string` or
+    /// `an object`
+    pub actual: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for FieldTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field value is {}: {}", self.actual, self.message)
+    }
+}
+
+impl std::error::Error for FieldTypeError {}
+
+impl RowFieldValue {
+    /// Decode the field value as a value of type `T`
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, FieldTypeError> {
+        T::deserialize(&self.0).map_err(|err| FieldTypeError {
+            actual: json_type(&self.0),
+            message: err.to_string(),
+        })
+    }
+
+    pub fn as_string(&self) -> Result<String, FieldTypeError> {
+        self.decode()
+    }
+
+    pub fn as_i64(&self) -> Result<i64, FieldTypeError> {
+        self.decode()
+    }
+
+    pub fn as_f64(&self) -> Result<f64, FieldTypeError> {
+        self.decode()
+    }
+
+    pub fn as_bool(&self) -> Result<bool, FieldTypeError> {
+        self.decode()
+    }
+}
+
+pub(crate) fn json_type(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
+impl RowSet {
+    /// Decode the aggregate named `key` as a value of type `T`.
+    ///
+    /// Some aggregates (for example, `max` over an empty row set) may be `null`,
+    /// which can be handled by requesting an `Option`.
+    pub fn aggregate<T: DeserializeOwned>(&self, key: &str) -> Result<T, AggregateError> {
+        let value = self
+            .aggregates
+            .as_ref()
+            .and_then(|aggregates| aggregates.get(key))
+            .ok_or_else(|| AggregateError::Missing { key: key.into() })?;
+
+        T::deserialize(value).map_err(|err| AggregateError::InvalidValue {
+            key: key.into(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Read an integer aggregate, such as a count
+    pub fn aggregate_i64(&self, key: &str) -> Result<i64, AggregateError> {
+        self.aggregate(key)
+    }
+
+    /// Read a numeric aggregate, such as a sum or an average
+    pub fn aggregate_f64(&self, key: &str) -> Result<f64, AggregateError> {
+        self.aggregate(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rowset() -> RowSet {
+        RowSet {
+            aggregates: Some(IndexMap::from_iter([
+                ("count".into(), json!(3)),
+                ("avg".into(), json!(1.5)),
+                ("max".into(), serde_json::Value::Null),
+            ])),
+            rows: None,
+        }
+    }
+
+    #[test]
+    fn test_row_field_value_accessors() {
+        assert_eq!(
+            RowFieldValue(json!("title")).as_string(),
+            Ok("title".into())
+        );
+        assert_eq!(RowFieldValue(json!(42)).as_i64(), Ok(42));
+        assert_eq!(RowFieldValue(json!(42)).as_f64(), Ok(42.0));
+        assert_eq!(RowFieldValue(json!(true)).as_bool(), Ok(true));
+        assert_eq!(
+            RowFieldValue(json!([1, 2])).decode::<Vec<i64>>(),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(
+            RowFieldValue(serde_json::Value::Null).decode::<Option<String>>(),
+            Ok(None)
+        );
+
+        let err = RowFieldValue(json!({ "id": 1 })).as_i64().unwrap_err();
+        assert_eq!(err.actual, "an object");
+        assert_eq!(
+            RowFieldValue(json!("1")).as_i64().unwrap_err().actual,
+            "a string"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_accessors() {
+        let rowset = rowset();
+
+        assert_eq!(rowset.aggregate_i64("count"), Ok(3));
+        assert_eq!(rowset.aggregate_f64("count"), Ok(3.0));
+        assert_eq!(rowset.aggregate_f64("avg"), Ok(1.5));
+        assert_eq!(rowset.aggregate::<Option<i64>>("max"), Ok(None));
+    }
+
+    #[test]
+    fn test_aggregate_errors() {
+        let rowset = rowset();
+
+        assert_eq!(
+            rowset.aggregate_i64("sum"),
+            Err(AggregateError::Missing { key: "sum".into() })
+        );
+        assert!(matches!(
+            rowset.aggregate_i64("avg"),
+            Err(AggregateError::InvalidValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+use std::fmt;
+
+use crate::visit::RequestVisitor;
+use crate::*;
+
+/// An error splitting a request with [`explode_variables`], or combining the
+/// responses with [`reassemble_row_sets`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VariablesError {
+    /// The request refers to a variable which is missing from one of its variable sets
+    UndefinedVariable { index: usize, name: VariableName },
+    /// The response to one of the split requests did not contain exactly one row set
+    UnexpectedRowSetCount { index: usize, count: usize },
+}
+
+impl fmt::Display for VariablesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariablesError::UndefinedVariable { index, name } => {
+                write!(f, "variable {name} is not defined in variable set {index}")
+            }
+            VariablesError::UnexpectedRowSetCount { index, count } => write!(
+                f,
+                "expected one row set in response {index}, but received {count}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VariablesError {}
+
+/// Split a request with variables into one request for each variable set, with the
+/// values of the variables inlined as literals, for connectors which do not support
+/// the `variables` capability. The requests can be sent concurrently, and their
+/// responses combined in order with [`reassemble_row_sets`].
+///
+/// A request without variables is returned unchanged, as a single request.
+pub fn explode_variables(request: &QueryRequest) -> Result<Vec<QueryRequest>, VariablesError> {
+    let Some(variable_sets) = &request.variables else {
+        return Ok(vec![request.clone()]);
+    };
+
+    variable_sets
+        .iter()
+        .enumerate()
+        .map(|(index, variables)| {
+            let mut request = QueryRequest {
+                variables: None,
+                ..request.clone()
+            };
+            Inliner { index, variables }.request(&mut request)?;
+            Ok(request)
+        })
+        .collect()
+}
+
+/// Combine the responses to the requests returned by [`explode_variables`], in the
+/// same order, into the response to the original request
+pub fn reassemble_row_sets(
+    responses: impl IntoIterator<Item = QueryResponse>,
+) -> Result<QueryResponse, VariablesError> {
+    responses
+        .into_iter()
+        .enumerate()
+        .map(
+            |(index, QueryResponse(mut row_sets))| match row_sets.len() {
+                1 => Ok(row_sets.remove(0)),
+                count => Err(VariablesError::UnexpectedRowSetCount { index, count }),
+            },
+        )
+        .collect::<Result<_, _>>()
+        .map(QueryResponse)
+}
+
+/// The names of all variables referred to anywhere in a request: in its arguments,
+/// field arguments, relationship arguments, comparison values and nested queries
+pub fn referenced_variables(request: &QueryRequest) -> BTreeSet<VariableName> {
+    let mut collector = Collector::default();
+    // The visitor walks the request mutably, but the collector never replaces anything
+    match collector.request(&mut request.clone()) {
+        Ok(()) => collector.names,
+        Err(never) => match never {},
+    }
+}
+
+/// Replaces references to variables with their values from one variable set
+struct Inliner<'a> {
+    index: usize,
+    variables: &'a BTreeMap<VariableName, serde_json::Value>,
+}
+
+impl RequestVisitor for Inliner<'_> {
+    type Error = VariablesError;
+
+    fn variable(
+        &mut self,
+        name: &VariableName,
+    ) -> Result<Option<serde_json::Value>, VariablesError> {
+        self.variables.get(name).cloned().map(Some).ok_or_else(|| {
+            VariablesError::UndefinedVariable {
+                index: self.index,
+                name: name.clone(),
+            }
+        })
+    }
+}
+
+/// Collects the names of referenced variables, leaving the request unchanged
+#[derive(Default)]
+struct Collector {
+    names: BTreeSet<VariableName>,
+}
+
+impl RequestVisitor for Collector {
+    type Error = Infallible;
+
+    fn variable(&mut self, name: &VariableName) -> Result<Option<serde_json::Value>, Infallible> {
+        self.names.insert(name.clone());
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::*;
+
+    fn request() -> QueryRequest {
+        serde_json::from_value(serde_json::json!({
+            "collection": "articles_by_author",
+            "arguments": { "author_id": { "type": "variable", "name": "id" } },
+            "query": {
+                "fields": { "title": { "type": "column", "column": "title" } },
+                "predicate": {
+                    "type": "binary_comparison_operator",
+                    "column": { "type": "column", "name": "title", "path": [] },
+                    "operator": "like",
+                    "value": { "type": "variable", "name": "search" }
+                }
+            },
+            "collection_relationships": {},
+            "variables": [
+                { "id": 1, "search": "Functional" },
+                { "id": 2, "search": "Rust" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_explode_variables() {
+        let requests = explode_variables(&request()).unwrap();
+        assert_eq!(requests.len(), 2);
+
+        let second = &requests[1];
+        assert_eq!(second.variables, None);
+        assert_eq!(
+            second.arguments[&ArgumentName::from("author_id")],
+            Argument::literal(2)
+        );
+        assert!(matches!(
+            &second.query.predicate,
+            Some(Expression::BinaryComparisonOperator {
+                value: ComparisonValue::Scalar { value },
+                ..
+            }) if value == "Rust"
+        ));
+    }
+
+    #[test]
+    fn test_explode_undefined_variable() {
+        let mut request = request();
+        request.variables = Some(vec![BTreeMap::from([("id".into(), 1.into())])]);
+        assert_eq!(
+            explode_variables(&request),
+            Err(VariablesError::UndefinedVariable {
+                index: 0,
+                name: "search".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_referenced_variables() {
+        let mut request = request();
+        request.collection_relationships.insert(
+            "article_author".into(),
+            Relationship {
+                column_mapping: BTreeMap::new(),
+                relationship_type: RelationshipType::Object,
+                target_collection: "authors".into(),
+                arguments: BTreeMap::from([(
+                    "since".into(),
+                    RelationshipArgument::Variable {
+                        name: "since".into(),
+                    },
+                )]),
+            },
+        );
+
+        assert_eq!(
+            referenced_variables(&request),
+            BTreeSet::from(["id".into(), "search".into(), "since".into()])
+        );
+    }
+
+    #[test]
+    fn test_reassemble_row_sets() {
+        let row_set = |title: &str| RowSet {
+            aggregates: None,
+            rows: Some(vec![IndexMap::from([(
+                "title".into(),
+                RowFieldValue(title.into()),
+            )])]),
+        };
+
+        assert_eq!(
+            reassemble_row_sets([
+                QueryResponse(vec![row_set("a")]),
+                QueryResponse(vec![row_set("b")]),
+            ]),
+            Ok(QueryResponse(vec![row_set("a"), row_set("b")]))
+        );
+        assert_eq!(
+            reassemble_row_sets([QueryResponse(vec![])]),
+            Err(VariablesError::UnexpectedRowSetCount { index: 0, count: 0 })
+        );
+    }
+}
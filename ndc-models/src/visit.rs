@@ -0,0 +1,193 @@
+use crate::*;
+
+/// Walks every argument and predicate of a request, including those of nested
+/// fields, relationships, paths and `exists` expressions, so that each analysis or
+/// rewrite of a request only needs to say what to do with the parts it cares about.
+///
+/// By default, each reference to a variable is passed to
+/// [`RequestVisitor::variable`], and replaced with a literal if it returns a value.
+/// Variable sets themselves are not visited.
+pub(crate) trait RequestVisitor {
+    type Error;
+
+    /// Called for each reference to a variable by the default hooks
+    fn variable(&mut self, _name: &VariableName) -> Result<Option<serde_json::Value>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Called for each argument to a collection or field
+    fn argument(
+        &mut self,
+        _name: &ArgumentName,
+        argument: &mut Argument,
+    ) -> Result<(), Self::Error> {
+        if let Argument::Variable { name } = argument {
+            if let Some(value) = self.variable(name)? {
+                *argument = Argument::Literal { value };
+            }
+        }
+        Ok(())
+    }
+
+    /// Called for each argument to a relationship or `exists` collection
+    fn relationship_argument(
+        &mut self,
+        _name: &ArgumentName,
+        argument: &mut RelationshipArgument,
+    ) -> Result<(), Self::Error> {
+        if let RelationshipArgument::Variable { name } = argument {
+            if let Some(value) = self.variable(name)? {
+                *argument = RelationshipArgument::Literal { value };
+            }
+        }
+        Ok(())
+    }
+
+    /// Called for the value of each binary comparison, along with the column it is
+    /// compared against. Paths in either are walked separately.
+    fn comparison_value(
+        &mut self,
+        _column: &ComparisonTarget,
+        value: &mut ComparisonValue,
+    ) -> Result<(), Self::Error> {
+        if let ComparisonValue::Variable { name } = value {
+            if let Some(scalar) = self.variable(name)? {
+                *value = ComparisonValue::Scalar { value: scalar };
+            }
+        }
+        Ok(())
+    }
+
+    fn request(&mut self, request: &mut QueryRequest) -> Result<(), Self::Error> {
+        self.arguments(&mut request.arguments)?;
+        self.query(&mut request.query)?;
+        for relationship in request.collection_relationships.values_mut() {
+            self.relationship_arguments(&mut relationship.arguments)?;
+        }
+        Ok(())
+    }
+
+    fn arguments(
+        &mut self,
+        arguments: &mut BTreeMap<ArgumentName, Argument>,
+    ) -> Result<(), Self::Error> {
+        for (name, argument) in arguments {
+            self.argument(name, argument)?;
+        }
+        Ok(())
+    }
+
+    fn relationship_arguments(
+        &mut self,
+        arguments: &mut BTreeMap<ArgumentName, RelationshipArgument>,
+    ) -> Result<(), Self::Error> {
+        for (name, argument) in arguments {
+            self.relationship_argument(name, argument)?;
+        }
+        Ok(())
+    }
+
+    fn query(&mut self, query: &mut Query) -> Result<(), Self::Error> {
+        for field in query.fields.iter_mut().flat_map(IndexMap::values_mut) {
+            self.field(field)?;
+        }
+        for element in query
+            .order_by
+            .iter_mut()
+            .flat_map(|order_by| &mut order_by.elements)
+        {
+            let path = match &mut element.target {
+                OrderByTarget::Column { path, .. }
+                | OrderByTarget::SingleColumnAggregate { path, .. }
+                | OrderByTarget::StarCountAggregate { path } => path,
+            };
+            self.path(path)?;
+        }
+        if let Some(predicate) = &mut query.predicate {
+            self.expression(predicate)?;
+        }
+        Ok(())
+    }
+
+    fn field(&mut self, field: &mut Field) -> Result<(), Self::Error> {
+        match field {
+            Field::Column {
+                fields, arguments, ..
+            } => {
+                self.arguments(arguments)?;
+                if let Some(fields) = fields {
+                    self.nested_field(fields)?;
+                }
+            }
+            Field::Relationship {
+                query, arguments, ..
+            } => {
+                self.relationship_arguments(arguments)?;
+                self.query(query)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn nested_field(&mut self, field: &mut NestedField) -> Result<(), Self::Error> {
+        match field {
+            NestedField::Object(object) => {
+                for field in object.fields.values_mut() {
+                    self.field(field)?;
+                }
+                Ok(())
+            }
+            NestedField::Array(array) => self.nested_field(&mut array.fields),
+        }
+    }
+
+    fn path(&mut self, path: &mut [PathElement]) -> Result<(), Self::Error> {
+        for element in path {
+            self.relationship_arguments(&mut element.arguments)?;
+            if let Some(predicate) = &mut element.predicate {
+                self.expression(predicate)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn comparison_target(&mut self, target: &mut ComparisonTarget) -> Result<(), Self::Error> {
+        match target {
+            ComparisonTarget::Column { path, .. } => self.path(path),
+            ComparisonTarget::RootCollectionColumn { .. } => Ok(()),
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expression) -> Result<(), Self::Error> {
+        match expression {
+            Expression::And { expressions } | Expression::Or { expressions } => {
+                for expression in expressions {
+                    self.expression(expression)?;
+                }
+            }
+            Expression::Not { expression } => self.expression(expression)?,
+            Expression::UnaryComparisonOperator { column, .. } => {
+                self.comparison_target(column)?;
+            }
+            Expression::BinaryComparisonOperator { column, value, .. } => {
+                self.comparison_target(column)?;
+                self.comparison_value(column, value)?;
+                if let ComparisonValue::Column { column } = value {
+                    self.comparison_target(column)?;
+                }
+            }
+            Expression::Exists {
+                in_collection,
+                predicate,
+            } => {
+                let (ExistsInCollection::Related { arguments, .. }
+                | ExistsInCollection::Unrelated { arguments, .. }) = in_collection;
+                self.relationship_arguments(arguments)?;
+                if let Some(predicate) = predicate {
+                    self.expression(predicate)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
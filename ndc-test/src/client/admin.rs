@@ -0,0 +1,91 @@
+use reqwest::header::HeaderMap;
+
+use super::{
+    append_path, call_with_retries, prepare_request, rewrite_url, send_request_with,
+    serialize_body, Configuration, Error,
+};
+
+/// Send a POST request to an administrative path under the base path, for example
+/// `admin_post(&configuration, "admin/cache/clear", &body)` for a connector which
+/// serves `POST /admin/cache/clear`.
+///
+/// The specification does not define any administrative endpoints, so the path and
+/// the shape of the body and response are up to the connector. The request is sent
+/// and its response read in the same way as for the other endpoints, with the same
+/// authentication, headers, preflight and response size limit. Since it may have
+/// side effects, it is only retried if the retry policy retries mutations, and it
+/// fails with [`Error::ReadOnlyConfiguration`] if the configuration is read-only. An
+/// empty response body is returned as `null`.
+pub async fn admin_post<B: serde::Serialize>(
+    configuration: &Configuration,
+    path: &str,
+    body: &B,
+) -> Result<serde_json::Value, Error> {
+    if configuration.read_only {
+        return Err(Error::ReadOnlyConfiguration);
+    }
+
+    let uri = &admin_url(configuration, path)?;
+    let body = serialize_body(configuration, body)?;
+    let policy = configuration
+        .retry_policy
+        .as_ref()
+        .filter(|policy| policy.retry_mutations);
+
+    let send = move |body: Option<Vec<u8>>, credential: usize| {
+        Ok(prepare_request(
+            configuration,
+            reqwest::Method::POST,
+            uri.clone(),
+            configuration.request_timeout,
+            body,
+            configuration.credentials.get(credential),
+        ))
+    };
+    let with_metadata = call_with_retries(
+        policy,
+        Some(body),
+        HeaderMap::new(),
+        |body, headers, first_attempt| {
+            send_request_with(configuration, None, body, headers, first_attempt, send)
+        },
+    )
+    .await?;
+    Ok(with_metadata.response)
+}
+
+fn admin_url(configuration: &Configuration, path: &str) -> Result<reqwest::Url, Error> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let url =
+        append_path(&configuration.base_path, &segments).map_err(|()| Error::InvalidBaseURL)?;
+    rewrite_url(configuration, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{admin_post, admin_url};
+    use crate::client::{Configuration, Error};
+
+    #[test]
+    fn test_admin_url() {
+        let url = reqwest::Url::parse("http://localhost:8100/ndc/").unwrap();
+        let configuration = Configuration::builder(url).build().unwrap();
+        assert_eq!(
+            admin_url(&configuration, "/admin/cache/clear")
+                .unwrap()
+                .as_str(),
+            "http://localhost:8100/ndc/admin/cache/clear"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_post_read_only() {
+        // Nothing listens here, so the call must fail before contacting the connector
+        let url = reqwest::Url::parse("http://localhost:1/").unwrap();
+        let configuration = Configuration::builder(url).read_only(true).build().unwrap();
+        assert!(matches!(
+            admin_post(&configuration, "admin/reset", &serde_json::json!({})).await,
+            Err(Error::ReadOnlyConfiguration)
+        ));
+    }
+}
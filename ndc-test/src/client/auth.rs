@@ -0,0 +1,242 @@
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderName, HeaderValue};
+
+use super::{Configuration, Error};
+
+/// A credential sent with every request to the connector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthCredential {
+    /// A bearer token, sent in the `Authorization` header
+    Bearer(String),
+    /// Any other header, for example an API key
+    Header(HeaderName, HeaderValue),
+}
+
+impl AuthCredential {
+    pub(crate) fn apply(&self, req_builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            AuthCredential::Bearer(token) => req_builder.bearer_auth(token),
+            AuthCredential::Header(name, value) => {
+                let mut value = value.clone();
+                value.set_sensitive(true);
+                req_builder.header(name, value)
+            }
+        }
+    }
+}
+
+/// The error type returned by an [`AuthProvider`], which is reported as
+/// [`Error::AuthRefresh`]
+pub type AuthProviderError = Box<dyn error::Error + Send + Sync>;
+
+/// Fetches bearer tokens which expire, for example with an OAuth client credentials
+/// flow.
+///
+/// The client calls [`AuthProvider::token`] before its first request, and caches the
+/// token until the connector rejects it with a 401. Then it fetches a fresh token
+/// and retries the request once.
+#[async_trait]
+pub trait AuthProvider: fmt::Debug + Send + Sync {
+    async fn token(&self) -> Result<String, AuthProviderError>;
+}
+
+/// The token last fetched from the configuration's [`AuthProvider`], shared by every
+/// clone of a configuration
+pub type AuthTokenCache = Arc<tokio::sync::Mutex<Option<String>>>;
+
+/// The cached token, fetching one first if there is none. Returns `None` if the
+/// configuration has no [`AuthProvider`].
+pub(crate) async fn cached_token(configuration: &Configuration) -> Result<Option<String>, Error> {
+    let Some(auth_refresh) = &configuration.auth_refresh else {
+        return Ok(None);
+    };
+    let mut cached = configuration.auth_token_cache.lock().await;
+    if cached.is_none() {
+        *cached = Some(auth_refresh.token().await.map_err(Error::AuthRefresh)?);
+    }
+    Ok(cached.clone())
+}
+
+/// Add the cached token to a request, for requests which are not retried when the
+/// token is rejected
+pub(crate) async fn authorize(
+    configuration: &Configuration,
+    req_builder: reqwest::RequestBuilder,
+) -> Result<reqwest::RequestBuilder, Error> {
+    Ok(match cached_token(configuration).await? {
+        Some(token) => req_builder.bearer_auth(token),
+        None => req_builder,
+    })
+}
+
+/// Replace `rejected` with a fresh token.
+///
+/// The cache stays locked while the token is fetched, so concurrent calls wait for
+/// the same refresh. Callers which were rejected with a token which has already been
+/// replaced are given the new token, rather than fetching another.
+pub(crate) async fn refresh_token(
+    configuration: &Configuration,
+    rejected: &str,
+) -> Result<Option<String>, Error> {
+    let Some(auth_refresh) = &configuration.auth_refresh else {
+        return Ok(None);
+    };
+    let mut cached = configuration.auth_token_cache.lock().await;
+    if cached.as_deref().is_some_and(|token| token != rejected) {
+        return Ok(cached.clone());
+    }
+    // Forget the rejected token even if the refresh fails, so that the next call
+    // tries again
+    *cached = None;
+    *cached = Some(auth_refresh.token().await.map_err(Error::AuthRefresh)?);
+    Ok(cached.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use super::{cached_token, refresh_token, AuthCredential, AuthProvider, AuthProviderError};
+    use crate::client::{admin_post, build_request_with_credential, Configuration, Endpoint};
+
+    #[derive(Debug, Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AuthProvider for CountingProvider {
+        async fn token(&self) -> Result<String, AuthProviderError> {
+            // Give concurrent refreshes a chance to overlap
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("token-{call}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_refresh() {
+        let provider = Arc::new(CountingProvider::default());
+        let url = reqwest::Url::parse("http://hasura.io").unwrap();
+        let configuration = Configuration::builder(url)
+            .auth_refresh(Some(provider.clone()))
+            .build()
+            .unwrap();
+
+        // The first token is cached, and shared by clones
+        let token = cached_token(&configuration).await.unwrap();
+        assert_eq!(token.as_deref(), Some("token-0"));
+        let token = cached_token(&configuration.clone()).await.unwrap();
+        assert_eq!(token.as_deref(), Some("token-0"));
+
+        // Concurrent calls rejected with the same token share one refresh
+        let (first, second) = tokio::join!(
+            refresh_token(&configuration, "token-0"),
+            refresh_token(&configuration, "token-0"),
+        );
+        assert_eq!(first.unwrap().as_deref(), Some("token-1"));
+        assert_eq!(second.unwrap().as_deref(), Some("token-1"));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_credential_headers() {
+        let url = reqwest::Url::parse("http://hasura.io").unwrap();
+        let configuration = Configuration::builder(url)
+            .credentials(vec![
+                AuthCredential::Bearer("primary".into()),
+                AuthCredential::Header("x-api-key".parse().unwrap(), "secondary".parse().unwrap()),
+            ])
+            .build()
+            .unwrap();
+
+        let request = build_request_with_credential(&configuration, Endpoint::Query, None, 0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer primary");
+        assert!(!request.headers().contains_key("x-api-key"));
+
+        let request = build_request_with_credential(&configuration, Endpoint::Query, None, 1)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["x-api-key"], "secondary");
+        assert!(!request.headers().contains_key("authorization"));
+    }
+
+    /// Serve `requests` requests on a fresh connection each, accepting only
+    /// `token`, and record the `Authorization` header of each
+    fn serve(requests: usize, token: &'static str) -> (reqwest::Url, Arc<Mutex<Vec<String>>>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = reqwest::Url::parse(&format!("http://{}/", listener.local_addr().unwrap()));
+        let seen = Arc::new(Mutex::new(vec![]));
+        let recorded = seen.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut reader = BufReader::new(stream.unwrap());
+                let (mut authorization, mut content_length) = (String::new(), 0);
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    let (name, value) = line.split_once(": ").unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "authorization" => authorization = value.to_owned(),
+                        "content-length" => content_length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                reader
+                    .by_ref()
+                    .take(content_length)
+                    .read_to_end(&mut vec![])
+                    .unwrap();
+
+                let status = if authorization == format!("Bearer {token}") {
+                    "200 OK"
+                } else {
+                    "401 Unauthorized"
+                };
+                recorded.lock().unwrap().push(authorization);
+                let response =
+                    format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url.unwrap(), seen)
+    }
+
+    #[tokio::test]
+    async fn test_auth_refresh_admin_and_probe() {
+        let (url, seen) = serve(3, "token-1");
+        let configuration = Configuration::builder(url)
+            .auth_refresh(Some(Arc::new(CountingProvider::default())))
+            .build()
+            .unwrap();
+
+        // The first token is rejected, so the admin call refreshes it and retries
+        let response = admin_post(&configuration, "admin/cache/clear", &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(response, serde_json::Value::Null);
+        configuration.probe().await.unwrap();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["Bearer token-0", "Bearer token-1", "Bearer token-1"]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Calls for code which does not run inside an async runtime.
+//!
+//! Each call runs on a dedicated runtime, created the first time it is needed, and
+//! blocks the calling thread until the call completes. Since the call never runs on
+//! the caller's runtime, there is no risk of the "cannot start a runtime from within
+//! a runtime" panic. However, calling these functions from async code still blocks
+//! an executor thread for the duration of the call, so async code should use the
+//! async functions instead.
+
+use std::future::Future;
+use std::sync::{mpsc, OnceLock};
+
+use super::{Configuration, Error};
+
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .thread_name("ndc-client-blocking")
+            .enable_all()
+            .build()
+            .expect("failed to start the blocking client runtime")
+    })
+}
+
+/// Run `future` to completion on the dedicated runtime, blocking the current thread
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (sender, receiver) = mpsc::sync_channel(1);
+    runtime().spawn(async move {
+        // The receiver only goes away if the caller's thread has panicked
+        let _ = sender.send(future.await);
+    });
+    receiver
+        .recv()
+        .expect("the blocking client runtime dropped a call")
+}
+
+pub fn capabilities_get_blocking(
+    configuration: &Configuration,
+) -> Result<ndc_models::CapabilitiesResponse, Error> {
+    let configuration = configuration.clone();
+    block_on(async move { super::capabilities_get(&configuration).await })
+}
+
+pub fn mutation_post_blocking(
+    configuration: &Configuration,
+    mutation_request: ndc_models::MutationRequest,
+) -> Result<ndc_models::MutationResponse, Error> {
+    let configuration = configuration.clone();
+    block_on(async move { super::mutation_post(&configuration, mutation_request).await })
+}
+
+pub fn query_post_blocking(
+    configuration: &Configuration,
+    query_request: ndc_models::QueryRequest,
+) -> Result<ndc_models::QueryResponse, Error> {
+    let configuration = configuration.clone();
+    block_on(async move { super::query_post(&configuration, query_request).await })
+}
+
+pub fn schema_get_blocking(
+    configuration: &Configuration,
+) -> Result<ndc_models::SchemaResponse, Error> {
+    let configuration = configuration.clone();
+    block_on(async move { super::schema_get(&configuration).await })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::block_on;
+
+    #[test]
+    fn test_block_on_without_runtime() {
+        assert_eq!(block_on(async { 1 + 1 }), 2);
+    }
+
+    #[tokio::test]
+    async fn test_block_on_inside_runtime() {
+        assert_eq!(block_on(async { 1 + 1 }), 2);
+    }
+}
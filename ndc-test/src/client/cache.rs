@@ -0,0 +1,179 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use indexmap::IndexMap;
+
+use super::{query_post, request_fingerprint, Configuration, Error};
+
+/// How a single call should use a [`QueryCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheControl {
+    /// Return a cached response if there is a fresh one, and cache the response
+    /// otherwise
+    #[default]
+    Use,
+    /// Always send the request, and cache the response
+    Refresh,
+    /// Always send the request, and leave the cache untouched
+    Bypass,
+}
+
+/// Wraps a [`Configuration`] with a bounded, in-memory cache of query responses,
+/// keyed by [`request_fingerprint`]. Responses are evicted when they are older than
+/// the TTL, or when they are the least recently used and the cache is full. Only
+/// successful responses are cached.
+///
+/// Query results can change at any time, so this should only be used where stale
+/// results are acceptable.
+#[derive(Debug, Clone)]
+pub struct QueryCache {
+    configuration: Configuration,
+    entries: Arc<Mutex<Lru>>,
+}
+
+impl QueryCache {
+    /// Cache up to `capacity` responses, each for at most `ttl`, if set
+    pub fn new(configuration: Configuration, capacity: usize, ttl: Option<Duration>) -> Self {
+        Self {
+            configuration,
+            entries: Arc::new(Mutex::new(Lru::new(capacity, ttl))),
+        }
+    }
+
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    pub async fn query_post(
+        &self,
+        query_request: ndc_models::QueryRequest,
+    ) -> Result<Arc<ndc_models::QueryResponse>, Error> {
+        self.query_post_with(query_request, CacheControl::Use).await
+    }
+
+    pub async fn query_post_with(
+        &self,
+        query_request: ndc_models::QueryRequest,
+        cache_control: CacheControl,
+    ) -> Result<Arc<ndc_models::QueryResponse>, Error> {
+        let key = request_fingerprint(&query_request)?;
+
+        if cache_control == CacheControl::Use {
+            if let Some(response) = self.entries.lock().unwrap().get(key, Instant::now()) {
+                return Ok(response);
+            }
+        }
+
+        let response = Arc::new(query_post(&self.configuration, query_request).await?);
+
+        if cache_control != CacheControl::Bypass {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key, response.clone(), Instant::now());
+        }
+
+        Ok(response)
+    }
+
+    /// Remove any cached response to this request, returning whether there was one
+    pub fn invalidate(&self, query_request: &ndc_models::QueryRequest) -> Result<bool, Error> {
+        let key = request_fingerprint(query_request)?;
+        Ok(self.entries.lock().unwrap().remove(key))
+    }
+
+    /// Remove every cached response
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Entries in least- to most-recently used order
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    ttl: Option<Duration>,
+    entries: IndexMap<u64, (Arc<ndc_models::QueryResponse>, Instant)>,
+}
+
+impl Lru {
+    fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: IndexMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: u64, now: Instant) -> Option<Arc<ndc_models::QueryResponse>> {
+        let (response, inserted) = self.entries.shift_remove(&key)?;
+        if self
+            .ttl
+            .is_some_and(|ttl| now.saturating_duration_since(inserted) >= ttl)
+        {
+            return None;
+        }
+        self.entries.insert(key, (response.clone(), inserted));
+        Some(response)
+    }
+
+    fn insert(&mut self, key: u64, response: Arc<ndc_models::QueryResponse>, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.shift_remove(&key);
+        while self.entries.len() >= self.capacity {
+            self.entries.shift_remove_index(0);
+        }
+        self.entries.insert(key, (response, now));
+    }
+
+    fn remove(&mut self, key: u64) -> bool {
+        self.entries.shift_remove(&key).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use super::Lru;
+
+    fn response() -> Arc<ndc_models::QueryResponse> {
+        Arc::new(ndc_models::QueryResponse(vec![]))
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let now = Instant::now();
+        let mut lru = Lru::new(2, None);
+        lru.insert(1, response(), now);
+        lru.insert(2, response(), now);
+        assert!(lru.get(1, now).is_some());
+        lru.insert(3, response(), now);
+
+        assert!(lru.get(1, now).is_some());
+        assert!(lru.get(2, now).is_none());
+        assert!(lru.get(3, now).is_some());
+    }
+
+    #[test]
+    fn test_lru_expires_entries() {
+        let now = Instant::now();
+        let mut lru = Lru::new(2, Some(Duration::from_secs(10)));
+        lru.insert(1, response(), now);
+
+        assert!(lru.get(1, now + Duration::from_secs(5)).is_some());
+        assert!(lru.get(1, now + Duration::from_secs(10)).is_none());
+        assert!(lru.entries.is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+use std::error;
+use std::fmt;
+
+/// The error type returned by a [`BodyCodec`], which is reported as
+/// [`super::Error::Codec`]
+pub type CodecError = Box<dyn error::Error + Send + Sync>;
+
+/// Encodes request bodies and decodes response bodies, for experimental connectors
+/// which accept a binary format such as CBOR or MessagePack instead of JSON.
+///
+/// Codecs work on JSON values, so that they can be stored in a [`super::Configuration`]
+/// and shared between requests of every type.
+pub trait BodyCodec: fmt::Debug + Send + Sync {
+    /// The media type sent as both `Content-Type` and `Accept`
+    fn content_type(&self) -> &str;
+
+    fn serialize(&self, body: &serde_json::Value) -> Result<Vec<u8>, CodecError>;
+
+    fn deserialize(&self, body: &[u8]) -> Result<serde_json::Value, CodecError>;
+}
+
+/// The JSON encoding defined by the specification. Configurations without a codec
+/// already use JSON, so this is mainly useful as a fallback for codecs which wrap
+/// another.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl BodyCodec for JsonCodec {
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+
+    fn serialize(&self, body: &serde_json::Value) -> Result<Vec<u8>, CodecError> {
+        Ok(serde_json::to_vec(body)?)
+    }
+
+    fn deserialize(&self, body: &[u8]) -> Result<serde_json::Value, CodecError> {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BodyCodec, JsonCodec};
+
+    #[test]
+    fn test_json_codec_round_trip() {
+        let value = serde_json::json!({"collection": "articles", "variables": []});
+        let bytes = JsonCodec.serialize(&value).unwrap();
+        assert_eq!(JsonCodec.deserialize(&bytes).unwrap(), value);
+        assert!(JsonCodec.deserialize(b"{").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use super::{capabilities_get, schema_get, Configuration, Error};
+
+/// A summary of a connector's capabilities and schema, suitable for printing
+#[derive(Debug, Clone)]
+pub struct ConnectorDescription {
+    pub version: String,
+    pub supported_features: Vec<&'static str>,
+    pub collection_count: usize,
+    pub function_count: usize,
+    pub procedure_count: usize,
+    pub scalar_types: Vec<String>,
+    pub capabilities: ndc_models::CapabilitiesResponse,
+    pub schema: ndc_models::SchemaResponse,
+}
+
+impl ConnectorDescription {
+    pub fn new(
+        capabilities: ndc_models::CapabilitiesResponse,
+        schema: ndc_models::SchemaResponse,
+    ) -> Self {
+        Self {
+            version: capabilities.version.clone(),
+            supported_features: capabilities.capabilities.supported_features(),
+            collection_count: schema.collections.len(),
+            function_count: schema.functions.len(),
+            procedure_count: schema.procedures.len(),
+            scalar_types: schema
+                .scalar_types
+                .keys()
+                .map(ToString::to_string)
+                .collect(),
+            capabilities,
+            schema,
+        }
+    }
+}
+
+impl fmt::Display for ConnectorDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Specification version: {}", self.version)?;
+        writeln!(
+            f,
+            "Supported features: {}",
+            list_or_none(&self.supported_features)
+        )?;
+        writeln!(f, "Collections: {}", self.collection_count)?;
+        writeln!(f, "Functions: {}", self.function_count)?;
+        writeln!(f, "Procedures: {}", self.procedure_count)?;
+        write!(f, "Scalar types: {}", list_or_none(&self.scalar_types))
+    }
+}
+
+fn list_or_none<T: AsRef<str>>(items: &[T]) -> String {
+    if items.is_empty() {
+        "none".into()
+    } else {
+        items
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Fetch a connector's capabilities and schema, and summarize them
+pub async fn describe(configuration: &Configuration) -> Result<ConnectorDescription, Error> {
+    let (capabilities, schema) =
+        futures::try_join!(capabilities_get(configuration), schema_get(configuration))?;
+    Ok(ConnectorDescription::new(capabilities, schema))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConnectorDescription;
+
+    #[test]
+    fn test_display() {
+        let capabilities = crate::client::default_capabilities();
+        let schema: ndc_models::SchemaResponse = serde_json::from_value(serde_json::json!({
+            "scalar_types": { "Int": { "aggregate_functions": {}, "comparison_operators": {} } },
+            "object_types": {},
+            "collections": [],
+            "functions": [],
+            "procedures": []
+        }))
+        .unwrap();
+
+        let description = ConnectorDescription::new(capabilities, schema);
+        assert_eq!(
+            description.to_string(),
+            format!(
+                "Specification version: {}\n\
+                 Supported features: none\n\
+                 Collections: 0\n\
+                 Functions: 0\n\
+                 Procedures: 0\n\
+                 Scalar types: Int",
+                ndc_models::VERSION
+            )
+        );
+    }
+}
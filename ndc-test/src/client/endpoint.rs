@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// The endpoints defined by the specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Endpoint {
+    Capabilities,
+    Schema,
+    Query,
+    QueryExplain,
+    Mutation,
+    MutationExplain,
+    Health,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 7] = [
+        Endpoint::Capabilities,
+        Endpoint::Schema,
+        Endpoint::Query,
+        Endpoint::QueryExplain,
+        Endpoint::Mutation,
+        Endpoint::MutationExplain,
+        Endpoint::Health,
+    ];
+
+    pub fn method(self) -> reqwest::Method {
+        match self {
+            Endpoint::Capabilities | Endpoint::Schema | Endpoint::Health => reqwest::Method::GET,
+            Endpoint::Query
+            | Endpoint::QueryExplain
+            | Endpoint::Mutation
+            | Endpoint::MutationExplain => reqwest::Method::POST,
+        }
+    }
+
+    /// The path segments of this endpoint, relative to the connector's base path
+    pub fn path(self) -> &'static [&'static str] {
+        match self {
+            Endpoint::Capabilities => &["capabilities"],
+            Endpoint::Schema => &["schema"],
+            Endpoint::Query => &["query"],
+            Endpoint::QueryExplain => &["query", "explain"],
+            Endpoint::Mutation => &["mutation"],
+            Endpoint::MutationExplain => &["mutation", "explain"],
+            Endpoint::Health => &["health"],
+        }
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} /{}", self.method(), self.path().join("/"))
+    }
+}
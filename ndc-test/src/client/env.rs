@@ -0,0 +1,164 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::{Configuration, ConfigurationBuilder, Error};
+
+impl Configuration {
+    /// Build a configuration from environment variables named with `prefix`:
+    ///
+    /// - `<PREFIX>_BASE_PATH` (required): the connector's base URL
+    /// - `<PREFIX>_AUTH_TOKEN`: a bearer token to send with every request
+    /// - `<PREFIX>_TIMEOUT_MS`, `<PREFIX>_QUERY_TIMEOUT_MS`,
+    ///   `<PREFIX>_MUTATION_TIMEOUT_MS`: timeouts, in milliseconds
+    /// - `<PREFIX>_MAX_RESPONSE_SIZE`: the largest response body to accept, in bytes
+    /// - `<PREFIX>_READ_ONLY`: `true` or `false`
+    ///
+    /// Missing or invalid variables, including variables which are not valid
+    /// Unicode, are reported with [`Error::InvalidEnvironmentVariable`].
+    pub fn from_env(prefix: &str) -> Result<Configuration, Error> {
+        builder_from_lookup(prefix, |name| match std::env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(err @ std::env::VarError::NotUnicode(_)) => Err(err.to_string()),
+        })?
+        .build()
+    }
+}
+
+/// Read the variables with `lookup`, which returns `None` if a variable is not set,
+/// or the reason a variable which is set can't be read
+fn builder_from_lookup(
+    prefix: &str,
+    lookup: impl Fn(&str) -> Result<Option<String>, String>,
+) -> Result<ConfigurationBuilder, Error> {
+    let vars = Vars { prefix, lookup };
+    let millis = |suffix: &str| -> Result<Option<Duration>, Error> {
+        Ok(vars.parse(suffix)?.map(Duration::from_millis))
+    };
+
+    let base_path = vars.parse::<reqwest::Url>("BASE_PATH")?.ok_or_else(|| {
+        Error::InvalidEnvironmentVariable {
+            name: vars.name("BASE_PATH"),
+            reason: "not set".into(),
+        }
+    })?;
+
+    Ok(Configuration::builder(base_path)
+        .auth_token(vars.get("AUTH_TOKEN")?)
+        .request_timeout(millis("TIMEOUT_MS")?)
+        .query_timeout(millis("QUERY_TIMEOUT_MS")?)
+        .mutation_timeout(millis("MUTATION_TIMEOUT_MS")?)
+        .max_response_size(vars.parse("MAX_RESPONSE_SIZE")?)
+        .read_only(vars.parse("READ_ONLY")?.unwrap_or(false)))
+}
+
+struct Vars<'a, F> {
+    prefix: &'a str,
+    lookup: F,
+}
+
+impl<F: Fn(&str) -> Result<Option<String>, String>> Vars<'_, F> {
+    fn name(&self, suffix: &str) -> String {
+        format!("{}_{suffix}", self.prefix)
+    }
+
+    fn get(&self, suffix: &str) -> Result<Option<String>, Error> {
+        let name = self.name(suffix);
+        (self.lookup)(&name).map_err(|reason| Error::InvalidEnvironmentVariable { name, reason })
+    }
+
+    fn parse<T>(&self, suffix: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.get(suffix)?
+            .map(|value| parse_var(&self.name(suffix), &value))
+            .transpose()
+    }
+}
+
+fn parse_var<T>(name: &str, value: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e: T::Err| Error::InvalidEnvironmentVariable {
+            name: name.into(),
+            reason: format!("invalid value {value:?}: {e}"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::{builder_from_lookup, Error};
+    use crate::client::AuthCredential;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Result<Option<String>, String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| ((*name).into(), (*value).into()))
+            .collect();
+        move |name| Ok(vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_from_env() {
+        let configuration = builder_from_lookup(
+            "NDC",
+            lookup(&[
+                ("NDC_BASE_PATH", "http://localhost:8100"),
+                ("NDC_AUTH_TOKEN", "secret"),
+                ("NDC_TIMEOUT_MS", "1500"),
+                ("NDC_READ_ONLY", "true"),
+            ]),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        assert_eq!(configuration.base_path.as_str(), "http://localhost:8100/");
+        assert_eq!(
+            configuration.credentials,
+            [AuthCredential::Bearer("secret".into())]
+        );
+        assert_eq!(
+            configuration.request_timeout,
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(configuration.query_timeout, None);
+        assert!(configuration.read_only);
+    }
+
+    #[test]
+    fn test_from_env_errors() {
+        assert!(matches!(
+            builder_from_lookup("NDC", lookup(&[])),
+            Err(Error::InvalidEnvironmentVariable { name, .. }) if name == "NDC_BASE_PATH"
+        ));
+        assert!(matches!(
+            builder_from_lookup(
+                "NDC",
+                lookup(&[
+                    ("NDC_BASE_PATH", "http://localhost:8100"),
+                    ("NDC_TIMEOUT_MS", "soon"),
+                ]),
+            ),
+            Err(Error::InvalidEnvironmentVariable { name, .. }) if name == "NDC_TIMEOUT_MS"
+        ));
+        assert!(matches!(
+            builder_from_lookup("NDC", |name| match name {
+                "NDC_BASE_PATH" => Ok(Some("http://localhost:8100".into())),
+                "NDC_AUTH_TOKEN" => Err("environment variable was not valid unicode".into()),
+                _ => Ok(None),
+            }),
+            Err(Error::InvalidEnvironmentVariable { name, .. }) if name == "NDC_AUTH_TOKEN"
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+use base64::Engine;
+use sha2::Digest;
+
+use super::Error;
+
+/// A digest of the response body, as advertised by the connector in a
+/// `Content-MD5` (RFC 1864) or `Digest` (RFC 3230) header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExpectedDigest {
+    Md5(String),
+    Sha256(String),
+    Sha512(String),
+}
+
+/// Collect any body digests advertised in the response headers. Algorithms we do
+/// not recognize are ignored.
+pub(crate) fn expected_digests(headers: &reqwest::header::HeaderMap) -> Vec<ExpectedDigest> {
+    let mut digests = vec![];
+
+    for value in headers.get_all("content-md5") {
+        if let Ok(value) = value.to_str() {
+            digests.push(ExpectedDigest::Md5(value.trim().into()));
+        }
+    }
+
+    for value in headers.get_all("digest") {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for instance in value.split(',') {
+            if let Some((algorithm, encoded)) = instance.trim().split_once('=') {
+                let encoded = encoded.trim().to_owned();
+                match algorithm.trim().to_ascii_lowercase().as_str() {
+                    "md5" => digests.push(ExpectedDigest::Md5(encoded)),
+                    "sha-256" => digests.push(ExpectedDigest::Sha256(encoded)),
+                    "sha-512" => digests.push(ExpectedDigest::Sha512(encoded)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    digests
+}
+
+/// Check the response body against every advertised digest
+pub(crate) fn verify_digests(digests: &[ExpectedDigest], body: &[u8]) -> Result<(), Error> {
+    for digest in digests {
+        let (algorithm, encoded, actual) = match digest {
+            ExpectedDigest::Md5(encoded) => ("MD5", encoded, md5::Md5::digest(body).to_vec()),
+            ExpectedDigest::Sha256(encoded) => {
+                ("SHA-256", encoded, sha2::Sha256::digest(body).to_vec())
+            }
+            ExpectedDigest::Sha512(encoded) => {
+                ("SHA-512", encoded, sha2::Sha512::digest(body).to_vec())
+            }
+        };
+
+        let expected = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| Error::IntegrityCheckFailed(format!("malformed {algorithm} digest")))?;
+
+        if expected != actual {
+            return Err(Error::IntegrityCheckFailed(format!(
+                "{algorithm} digest does not match response body"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::*;
+
+    #[test]
+    fn test_verify_content_md5() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-md5",
+            HeaderValue::from_static("mZFLkyvTelC5g8XnyQrpOw=="),
+        );
+        let digests = expected_digests(&headers);
+
+        assert!(verify_digests(&digests, b"{}").is_ok());
+        assert!(matches!(
+            verify_digests(&digests, b"{"),
+            Err(Error::IntegrityCheckFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_digest_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "digest",
+            HeaderValue::from_static(
+                "unknown=abc, SHA-256=RBNvo1WzZ4oRRq0W9+hknpT7T8If536DEMBg9hyq/4o=",
+            ),
+        );
+        let digests = expected_digests(&headers);
+
+        assert_eq!(
+            digests,
+            vec![ExpectedDigest::Sha256(
+                "RBNvo1WzZ4oRRq0W9+hknpT7T8If536DEMBg9hyq/4o=".into()
+            )]
+        );
+        assert!(verify_digests(&digests, b"{}").is_ok());
+        assert!(verify_digests(&digests, b"[]").is_err());
+    }
+}
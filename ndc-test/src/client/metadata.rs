@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName};
+
+/// Details of a single call to a connector, for metering and auditing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMetadata {
+    pub status: reqwest::StatusCode,
+    /// The size of the request body sent, in bytes
+    pub request_bytes: usize,
+    /// The size of the response body received, in bytes
+    pub response_bytes: usize,
+    /// The time from sending the request until the response body had been read
+    pub duration: Duration,
+    /// Whether the connector echoed the configured correlation token back, or `None`
+    /// if no token was sent
+    pub correlation_echoed: Option<bool>,
+    /// The `(name, duration)` pairs reported in the connector's `Server-Timing`
+    /// header, if any. Malformed entries, and entries without a duration, are skipped.
+    pub server_timing: Vec<(String, Duration)>,
+    /// The index of the credential in [`Configuration::credentials`] which the
+    /// connector accepted, or `None` if no credentials are configured
+    ///
+    /// [`Configuration::credentials`]: super::Configuration::credentials
+    pub credential: Option<usize>,
+    /// The response headers, except those listed in
+    /// [`Configuration::stripped_response_headers`]
+    ///
+    /// [`Configuration::stripped_response_headers`]: super::Configuration::stripped_response_headers
+    pub headers: HeaderMap,
+}
+
+/// A response from a connector, along with the [`ResponseMetadata`] of the call
+#[derive(Debug, Clone)]
+pub struct WithMetadata<T> {
+    pub response: T,
+    pub metadata: ResponseMetadata,
+}
+
+/// A copy of `headers` without any of the `stripped` headers
+pub(crate) fn strip_headers(headers: &HeaderMap, stripped: &[HeaderName]) -> HeaderMap {
+    let mut headers = headers.clone();
+    for name in stripped {
+        headers.remove(name);
+    }
+    headers
+}
+
+/// Parse every `Server-Timing` header value, for example `db;dur=42, app;dur=3.5`
+pub(crate) fn parse_server_timing(headers: &HeaderMap) -> Vec<(String, Duration)> {
+    headers
+        .get_all("server-timing")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(parse_server_timing_entry)
+        .collect()
+}
+
+fn parse_server_timing_entry(entry: &str) -> Option<(String, Duration)> {
+    let mut parts = entry.split(';').map(str::trim);
+    let name = parts.next().filter(|name| !name.is_empty())?;
+    let millis = parts.find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("dur")
+            .then(|| value.trim().trim_matches('"'))
+    })?;
+    let duration = Duration::try_from_secs_f64(millis.parse::<f64>().ok()? / 1000.0).ok()?;
+    Some((name.to_string(), duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::{parse_server_timing, strip_headers};
+
+    #[test]
+    fn test_parse_server_timing() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            "server-timing",
+            HeaderValue::from_static("db;dur=42, cache;desc=\"Cache Read\";dur=0.5, miss"),
+        );
+        headers.append(
+            "server-timing",
+            HeaderValue::from_static(";dur=1, app;dur=abc, total;dur=-1, plan;dur=\"7\""),
+        );
+
+        assert_eq!(
+            parse_server_timing(&headers),
+            [
+                ("db".to_string(), Duration::from_secs_f64(0.042)),
+                ("cache".to_string(), Duration::from_secs_f64(0.0005)),
+                ("plan".to_string(), Duration::from_secs_f64(0.007)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        headers.append("traceparent", HeaderValue::from_static("00-abc-def-01"));
+        headers.append("x-internal-id", HeaderValue::from_static("1"));
+        headers.append("x-internal-id", HeaderValue::from_static("2"));
+
+        let stripped = strip_headers(
+            &headers,
+            &[
+                "traceparent".parse().unwrap(),
+                "x-internal-id".parse().unwrap(),
+            ],
+        );
+        assert_eq!(stripped.len(), 1);
+        assert_eq!(stripped["content-type"], "application/json");
+    }
+}
@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+
+use super::RetryPolicy;
+
+/// A best-effort hint of how urgently a request should be handled, sent in the
+/// `Priority` header (RFC 9218). Connectors which don't prioritize requests will
+/// ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// For example, background exports
+    Low,
+    Normal,
+    /// For example, interactive queries
+    High,
+}
+
+impl Priority {
+    /// The value of the `Priority` header, using the RFC 9218 urgency parameter,
+    /// where lower values are more urgent
+    pub fn header_value(self) -> HeaderValue {
+        HeaderValue::from_static(match self {
+            Priority::Low => "u=5",
+            Priority::Normal => "u=3",
+            Priority::High => "u=1",
+        })
+    }
+}
+
+/// Replaces the configured retry policy for a single call. As with the configured
+/// policy, mutations are only retried if the policy's `retry_mutations` is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryOverride {
+    /// Make a single attempt, for example for a user-initiated action where a retry
+    /// would be surprising
+    Disabled,
+    /// Use the configured policy, or the default policy if none is configured, but
+    /// make at most this many attempts, including the first
+    MaxAttempts(u32),
+    /// Use this policy instead of the configured one
+    Policy(RetryPolicy),
+}
+
+/// Options which apply to a single call, for the `*_with_options` functions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallOptions {
+    pub priority: Option<Priority>,
+    /// Defaults to `None`, which uses the configured retry policy
+    pub retry: Option<RetryOverride>,
+}
+
+impl CallOptions {
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryOverride) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// The retry policy for the call, given the configured policy
+    pub(crate) fn retry_policy<'a>(
+        &'a self,
+        configured: Option<&'a RetryPolicy>,
+    ) -> Option<Cow<'a, RetryPolicy>> {
+        match &self.retry {
+            None => configured.map(Cow::Borrowed),
+            Some(RetryOverride::Disabled) => None,
+            Some(RetryOverride::MaxAttempts(max_attempts)) => Some(Cow::Owned(RetryPolicy {
+                max_attempts: *max_attempts,
+                ..configured.cloned().unwrap_or_default()
+            })),
+            Some(RetryOverride::Policy(policy)) => Some(Cow::Borrowed(policy)),
+        }
+    }
+
+    /// The extra headers to send with the request
+    pub(crate) fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(priority) = self.priority {
+            headers.insert("priority", priority.header_value());
+        }
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CallOptions, Priority, RetryOverride};
+    use crate::client::RetryPolicy;
+
+    #[test]
+    fn test_priority_header() {
+        assert!(CallOptions::default().headers().is_empty());
+
+        let headers = CallOptions::default().priority(Priority::High).headers();
+        assert_eq!(headers["priority"], "u=1");
+    }
+
+    #[test]
+    fn test_retry_override() {
+        let configured = RetryPolicy {
+            max_attempts: 5,
+            ..RetryPolicy::default()
+        };
+        let policy = |options: CallOptions, configured: Option<&RetryPolicy>| {
+            options
+                .retry_policy(configured)
+                .map(|policy| policy.max_attempts)
+        };
+
+        assert_eq!(policy(CallOptions::default(), Some(&configured)), Some(5));
+        assert_eq!(policy(CallOptions::default(), None), None);
+
+        let disabled = CallOptions::default().retry(RetryOverride::Disabled);
+        assert_eq!(policy(disabled, Some(&configured)), None);
+
+        let attempts = CallOptions::default().retry(RetryOverride::MaxAttempts(2));
+        assert_eq!(policy(attempts.clone(), Some(&configured)), Some(2));
+        assert_eq!(policy(attempts, None), Some(2));
+
+        let replaced = CallOptions::default().retry(RetryOverride::Policy(RetryPolicy::default()));
+        assert_eq!(policy(replaced, Some(&configured)), Some(3));
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use tokio::sync::OnceCell;
+
+use super::{
+    auth, construct_error, content_type, prepare_request, rewrite_url, Configuration, Error,
+};
+
+/// The result of an `OPTIONS` request to the connector's base path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightResponse {
+    pub status: reqwest::StatusCode,
+    /// The methods listed in the `Allow` header, if any
+    pub allow: Vec<reqwest::Method>,
+}
+
+/// Caches the preflight response, shared by every clone of a configuration
+pub type PreflightCache = Arc<OnceCell<PreflightResponse>>;
+
+/// Send an `OPTIONS` request to the connector's base path, unless one has already
+/// succeeded for this configuration. Failed probes are not cached, so the next call
+/// will try again.
+pub async fn preflight(configuration: &Configuration) -> Result<PreflightResponse, Error> {
+    configuration
+        .preflight_cache
+        .get_or_try_init(|| send_preflight(configuration))
+        .await
+        .cloned()
+}
+
+async fn send_preflight(configuration: &Configuration) -> Result<PreflightResponse, Error> {
+    let req = auth::authorize(configuration, build_preflight(configuration)?).await?;
+    let resp = req.send().await?;
+
+    let status = resp.status();
+    if status.is_client_error() || status.is_server_error() {
+        let content_type = content_type(&resp);
+        let body = resp.bytes().await?;
+        return Err(construct_error(
+            configuration,
+            status,
+            content_type.as_ref(),
+            &body,
+        ));
+    }
+
+    Ok(PreflightResponse {
+        status,
+        allow: parse_allow(resp.headers()),
+    })
+}
+
+/// Build the `OPTIONS` request, with the same headers and authentication as any
+/// other request. Its URL is the base path, after the URL rewriter. Endpoint
+/// overrides do not apply, since they replace the URLs of single endpoints rather
+/// than the base path.
+fn build_preflight(configuration: &Configuration) -> Result<reqwest::RequestBuilder, Error> {
+    let url = rewrite_url(configuration, configuration.base_path.clone())?;
+    Ok(prepare_request(
+        configuration,
+        reqwest::Method::OPTIONS,
+        url,
+        configuration.request_timeout,
+        None,
+        configuration.credentials.first(),
+    ))
+}
+
+fn parse_allow(headers: &reqwest::header::HeaderMap) -> Vec<reqwest::Method> {
+    headers
+        .get_all(reqwest::header::ALLOW)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|method| method.trim().parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue, ALLOW};
+
+    use super::{build_preflight, parse_allow};
+    use crate::client::{AuthCredential, Configuration, UrlRewriter};
+
+    #[test]
+    fn test_parse_allow() {
+        let mut headers = HeaderMap::new();
+        headers.append(ALLOW, HeaderValue::from_static("GET, POST"));
+        headers.append(ALLOW, HeaderValue::from_static("OPTIONS"));
+
+        assert_eq!(
+            parse_allow(&headers),
+            [
+                reqwest::Method::GET,
+                reqwest::Method::POST,
+                reqwest::Method::OPTIONS
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_preflight() {
+        let url = reqwest::Url::parse("http://localhost:8100/ndc/").unwrap();
+        let configuration = Configuration::builder(url)
+            .credentials(vec![AuthCredential::Bearer("secret".into())])
+            .url_rewriter(Some(UrlRewriter::new(|mut url| {
+                url.set_host(Some("gateway")).unwrap();
+                url
+            })))
+            .build()
+            .unwrap();
+
+        let request = build_preflight(&configuration).unwrap().build().unwrap();
+        assert_eq!(request.method(), reqwest::Method::OPTIONS);
+        assert_eq!(request.url().as_str(), "http://gateway:8100/ndc/");
+        assert_eq!(request.headers()["authorization"], "Bearer secret");
+    }
+}
@@ -0,0 +1,146 @@
+use std::error;
+use std::fmt;
+
+use super::{auth, build_request, construct_error, content_type, Configuration, Endpoint, Error};
+
+/// Why a connector could not be reached by [`Configuration::probe`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProbeFailure {
+    /// The host name of the base path could not be resolved
+    Dns,
+    /// Nothing is listening at the base path
+    ConnectionRefused,
+    /// A TLS connection could not be established, for example because of an
+    /// untrusted certificate
+    Tls,
+    /// The connector rejected the client's credentials
+    Unauthorized,
+    Timeout,
+    /// Any other transport failure
+    Other,
+}
+
+impl fmt::Display for ProbeFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProbeFailure::Dns => "host name could not be resolved",
+            ProbeFailure::ConnectionRefused => "connection refused",
+            ProbeFailure::Tls => "TLS handshake failed",
+            ProbeFailure::Unauthorized => "credentials were rejected",
+            ProbeFailure::Timeout => "request timed out",
+            ProbeFailure::Other => "request failed",
+        })
+    }
+}
+
+impl Configuration {
+    /// Check that the connector is reachable, by requesting the health endpoint, or
+    /// the capabilities endpoint if the connector does not implement health checks.
+    /// Intended to be called when a connector is registered, to fail fast with an
+    /// actionable [`Error::Unreachable`] rather than on the first query.
+    pub async fn probe(&self) -> Result<(), Error> {
+        let status = match probe_endpoint(self, Endpoint::Health).await? {
+            reqwest::StatusCode::NOT_FOUND
+            | reqwest::StatusCode::METHOD_NOT_ALLOWED
+            | reqwest::StatusCode::NOT_IMPLEMENTED => {
+                probe_endpoint(self, Endpoint::Capabilities).await?
+            }
+            status => status,
+        };
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedStatus { status })
+        }
+    }
+}
+
+/// Request `endpoint`, returning its status if it is successful, or one which
+/// [`Configuration::probe`] should interpret
+async fn probe_endpoint(
+    configuration: &Configuration,
+    endpoint: Endpoint,
+) -> Result<reqwest::StatusCode, Error> {
+    let req = build_request(configuration, endpoint, None)?;
+    let resp = auth::authorize(configuration, req)
+        .await?
+        .send()
+        .await
+        .map_err(|e| Error::Unreachable {
+            failure: classify(&e),
+            message: e.to_string(),
+        })?;
+
+    let status = resp.status();
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+            Err(Error::Unreachable {
+                failure: ProbeFailure::Unauthorized,
+                message: format!("{endpoint} returned status code {status}"),
+            })
+        }
+        reqwest::StatusCode::NOT_FOUND
+        | reqwest::StatusCode::METHOD_NOT_ALLOWED
+        | reqwest::StatusCode::NOT_IMPLEMENTED => Ok(status),
+        _ if status.is_client_error() || status.is_server_error() => {
+            let content_type = content_type(&resp);
+            let body = resp.bytes().await?;
+            Err(construct_error(
+                configuration,
+                status,
+                content_type.as_ref(),
+                &body,
+            ))
+        }
+        _ => Ok(status),
+    }
+}
+
+/// reqwest does not expose the cause of a connection failure directly, so this
+/// inspects the chain of underlying errors.
+fn classify(e: &reqwest::Error) -> ProbeFailure {
+    if e.is_timeout() {
+        return ProbeFailure::Timeout;
+    }
+
+    let mut source: Option<&(dyn error::Error + 'static)> = Some(e);
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            if io.kind() == std::io::ErrorKind::ConnectionRefused {
+                return ProbeFailure::ConnectionRefused;
+            }
+        }
+        let message = cause.to_string().to_lowercase();
+        if message.contains("dns error") || message.contains("failed to lookup address") {
+            return ProbeFailure::Dns;
+        }
+        if message.contains("certificate")
+            || message.contains("tls")
+            || message.contains("ssl")
+            || message.contains("handshake")
+        {
+            return ProbeFailure::Tls;
+        }
+        source = cause.source();
+    }
+
+    ProbeFailure::Other
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify, ProbeFailure};
+
+    #[tokio::test]
+    async fn test_classify_connection_refused() {
+        // Bind to find a free port, then close it so that nothing is listening
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let e = reqwest::get(format!("http://{addr}/health"))
+            .await
+            .unwrap_err();
+        assert_eq!(classify(&e), ProbeFailure::ConnectionRefused);
+    }
+}
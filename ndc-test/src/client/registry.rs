@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::{Configuration, ConfigurationBuilder, Error};
+
+#[derive(Debug)]
+struct Entry {
+    client: Arc<reqwest::Client>,
+    used: bool,
+}
+
+/// Shares HTTP clients, and so their connection pools, between configurations for
+/// the same connector.
+///
+/// Clients are keyed by the connector's base path and the settings used to build
+/// the client. Settings which only affect how responses are read, such as the error
+/// parser, are not part of the key.
+#[derive(Debug, Default)]
+pub struct ClientRegistry {
+    clients: Mutex<HashMap<u64, Entry>>,
+}
+
+impl ClientRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the client for the connector described by `builder`, building one if
+    /// there is none yet.
+    pub fn client(&self, builder: &ConfigurationBuilder) -> Result<Arc<reqwest::Client>, Error> {
+        let key = builder.client_key();
+        let mut clients = self.clients.lock().unwrap();
+
+        if let Some(entry) = clients.get_mut(&key) {
+            entry.used = true;
+            return Ok(entry.client.clone());
+        }
+
+        let client = Arc::new(builder.build_client()?);
+        clients.insert(
+            key,
+            Entry {
+                client: client.clone(),
+                used: true,
+            },
+        );
+        Ok(client)
+    }
+
+    /// Build a [`Configuration`] which uses the shared client for its connector
+    pub fn build(&self, builder: ConfigurationBuilder) -> Result<Configuration, Error> {
+        builder.validate()?;
+        let client = self.client(&builder)?;
+        Ok(builder.with_client(client.as_ref().clone()))
+    }
+
+    /// Remove every client which has not been requested since the previous call to
+    /// `evict_unused`, returning the number of clients removed.
+    ///
+    /// Call this after rebuilding the configurations for all connectors, so that
+    /// clients for connectors which have been removed are dropped.
+    pub fn evict_unused(&self) -> usize {
+        let mut clients = self.clients.lock().unwrap();
+        let before = clients.len();
+        clients.retain(|_, entry| std::mem::replace(&mut entry.used, false));
+        before - clients.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ClientRegistry;
+    use crate::client::Configuration;
+
+    fn builder(url: &str) -> crate::client::ConfigurationBuilder {
+        Configuration::builder(reqwest::Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_clients_are_shared_by_connector() {
+        let registry = ClientRegistry::new();
+
+        let a = registry.client(&builder("http://a:8100/")).unwrap();
+        let a_again = registry
+            .client(&builder("http://a:8100/").verify_body_integrity(true))
+            .unwrap();
+        let a_no_delay = registry
+            .client(&builder("http://a:8100/").tcp_nodelay(false))
+            .unwrap();
+        let b = registry.client(&builder("http://b:8100/")).unwrap();
+
+        assert!(Arc::ptr_eq(&a, &a_again));
+        assert!(!Arc::ptr_eq(&a, &a_no_delay));
+        assert!(!Arc::ptr_eq(&a, &b));
+        assert_eq!(registry.len(), 3);
+    }
+
+    #[test]
+    fn test_evict_unused() {
+        let registry = ClientRegistry::new();
+        registry.client(&builder("http://a:8100/")).unwrap();
+        registry.client(&builder("http://b:8100/")).unwrap();
+        assert_eq!(registry.evict_unused(), 0);
+
+        registry.client(&builder("http://a:8100/")).unwrap();
+        assert_eq!(registry.evict_unused(), 1);
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use super::{Endpoint, Error};
+
+/// Decides which failed requests are worth retrying.
+///
+/// Error responses are read and parsed in full before they are classified, so the
+/// classifier can inspect the connector's structured error, including the `code`
+/// field of its error details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryClassifier {
+    /// Retry responses with these status codes. Defaults to 429, 502, 503 and 504.
+    pub retryable_statuses: BTreeSet<u16>,
+    /// Retry error responses whose details contain one of these `code`s, whatever
+    /// their status, for example `deadlock_detected`
+    pub retryable_codes: BTreeSet<String>,
+    /// Retry requests which failed to connect or timed out. Defaults to `true`.
+    pub retry_transport_errors: bool,
+}
+
+impl Default for RetryClassifier {
+    fn default() -> Self {
+        Self {
+            retryable_statuses: BTreeSet::from([429, 502, 503, 504]),
+            retryable_codes: BTreeSet::new(),
+            retry_transport_errors: true,
+        }
+    }
+}
+
+impl RetryClassifier {
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error {
+            Error::ConnectorError(err) => {
+                self.retryable_statuses.contains(&err.status.as_u16())
+                    || err
+                        .error_code()
+                        .is_some_and(|code| self.retryable_codes.contains(code))
+            }
+            Error::InvalidConnectorError(err) => {
+                self.retryable_statuses.contains(&err.status.as_u16())
+            }
+            Error::Reqwest(err) => {
+                self.retry_transport_errors && (err.is_connect() || err.is_timeout())
+            }
+            _ => false,
+        }
+    }
+}
+
+/// How to randomize the delay between attempts, so that clients which failed at the
+/// same time don't all retry at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Jitter {
+    /// Wait for exactly the exponential backoff
+    None,
+    /// Wait for a random time between zero and the exponential backoff
+    Full,
+    /// Wait for half the exponential backoff, plus a random time up to the other half
+    Equal,
+    /// Wait for a random time between the initial backoff and three times the
+    /// previous delay, capped at the maximum backoff. This spreads retries out best
+    /// under contention.
+    #[default]
+    Decorrelated,
+}
+
+/// How to retry failed requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The most attempts to make, including the first
+    pub max_attempts: u32,
+    /// The delay before the first retry, which doubles after each attempt
+    pub initial_backoff: Duration,
+    /// The longest delay between attempts
+    pub max_backoff: Duration,
+    /// Also retry mutations. Only enable this for connectors whose mutations are
+    /// idempotent, since a failed response does not mean that the mutation was not
+    /// applied. Defaults to `false`.
+    pub retry_mutations: bool,
+    /// The longest time to spend on a call, across every attempt and the delays
+    /// between them. Once a retry would exceed it, the last error is returned as
+    /// [`Error::RetryBudgetExhausted`]. An attempt which is already in flight is not
+    /// cut short. Defaults to `None`.
+    pub total_deadline: Option<Duration>,
+    /// Defaults to [`Jitter::Decorrelated`]
+    pub jitter: Jitter,
+    /// Seed the random number generator used for jitter, so that delays are
+    /// reproducible, for example in tests. Defaults to `None`, which seeds it from
+    /// the operating system.
+    pub jitter_seed: Option<u64>,
+    pub classifier: RetryClassifier,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            retry_mutations: false,
+            total_deadline: None,
+            jitter: Jitter::default(),
+            jitter_seed: None,
+            classifier: RetryClassifier::default(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub(crate) fn applies_to(&self, endpoint: Endpoint) -> bool {
+        self.retry_mutations || endpoint != Endpoint::Mutation
+    }
+
+    /// The delays between the attempts of a single call
+    pub(crate) fn delays(&self) -> Delays<'_> {
+        let rng = match self.jitter_seed {
+            Some(seed) => SmallRng::seed_from_u64(seed),
+            None => SmallRng::from_entropy(),
+        };
+        Delays {
+            policy: self,
+            rng,
+            previous: self.initial_backoff,
+        }
+    }
+
+    /// The delay after the given attempt, counting from 1, before any jitter
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Whether there is time to wait `backoff` before another attempt, when `elapsed`
+    /// has already been spent on the call
+    pub(crate) fn within_deadline(&self, elapsed: Duration, backoff: Duration) -> bool {
+        self.total_deadline
+            .map_or(true, |deadline| elapsed.saturating_add(backoff) < deadline)
+    }
+}
+
+/// Computes the delay before each retry, with jitter
+pub(crate) struct Delays<'a> {
+    policy: &'a RetryPolicy,
+    rng: SmallRng,
+    previous: Duration,
+}
+
+impl Delays<'_> {
+    /// The delay after the given attempt, counting from 1
+    pub(crate) fn next(&mut self, attempt: u32) -> Duration {
+        let backoff = self.policy.backoff(attempt);
+        let delay = match self.policy.jitter {
+            Jitter::None => backoff,
+            Jitter::Full => self.rng.gen_range(Duration::ZERO..=backoff),
+            Jitter::Equal => backoff / 2 + self.rng.gen_range(Duration::ZERO..=backoff / 2),
+            Jitter::Decorrelated => {
+                let low = self.policy.initial_backoff;
+                let high = self.previous.saturating_mul(3).max(low);
+                self.rng.gen_range(low..=high).min(self.policy.max_backoff)
+            }
+        };
+        self.previous = delay;
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Jitter, RetryClassifier, RetryPolicy};
+    use crate::client::{ConnectorError, Error, InvalidConnectorError};
+
+    fn connector_error(status: u16, details: serde_json::Value) -> Error {
+        Error::ConnectorError(ConnectorError {
+            status: reqwest::StatusCode::from_u16(status).unwrap(),
+            error_response: ndc_models::ErrorResponse {
+                message: "error".into(),
+                details,
+            },
+        })
+    }
+
+    #[test]
+    fn test_classifier() {
+        let classifier = RetryClassifier {
+            retryable_codes: ["deadlock_detected".to_owned()].into(),
+            ..RetryClassifier::default()
+        };
+
+        assert!(classifier.is_retryable(&connector_error(503, serde_json::Value::Null)));
+        assert!(!classifier.is_retryable(&connector_error(500, serde_json::Value::Null)));
+        assert!(classifier.is_retryable(&connector_error(
+            500,
+            serde_json::json!({ "code": "deadlock_detected" })
+        )));
+        assert!(!classifier.is_retryable(&connector_error(
+            500,
+            serde_json::json!({ "code": "constraint_violation" })
+        )));
+        assert!(
+            classifier.is_retryable(&Error::InvalidConnectorError(InvalidConnectorError {
+                status: reqwest::StatusCode::BAD_GATEWAY,
+                content: serde_json::Value::Null,
+            }))
+        );
+        assert!(!classifier.is_retryable(&Error::InvalidBaseURL));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(40), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_within_deadline() {
+        let policy = RetryPolicy {
+            total_deadline: Some(Duration::from_secs(1)),
+            ..RetryPolicy::default()
+        };
+        let backoff = Duration::from_millis(100);
+
+        assert!(policy.within_deadline(Duration::from_millis(800), backoff));
+        assert!(!policy.within_deadline(Duration::from_millis(900), backoff));
+        assert!(RetryPolicy::default().within_deadline(Duration::MAX, backoff));
+    }
+
+    #[test]
+    fn test_jitter() {
+        let policy = |jitter| RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            jitter,
+            jitter_seed: Some(42),
+            ..RetryPolicy::default()
+        };
+
+        let none = policy(Jitter::None);
+        let mut delays = none.delays();
+        assert_eq!(delays.next(1), Duration::from_millis(100));
+        assert_eq!(delays.next(2), Duration::from_millis(200));
+
+        for attempt in 1..10 {
+            let full = policy(Jitter::Full);
+            assert!(full.delays().next(attempt) <= full.backoff(attempt));
+
+            let equal = policy(Jitter::Equal);
+            let delay = equal.delays().next(attempt);
+            assert!(delay >= equal.backoff(attempt) / 2 && delay <= equal.backoff(attempt));
+        }
+
+        let decorrelated = policy(Jitter::Decorrelated);
+        let mut delays = decorrelated.delays();
+        let mut previous = decorrelated.initial_backoff;
+        for attempt in 1..10 {
+            let delay = delays.next(attempt);
+            assert!(delay >= decorrelated.initial_backoff);
+            assert!(delay <= (previous * 3).min(decorrelated.max_backoff));
+            previous = delay;
+        }
+
+        // The same seed gives the same delays
+        let sequence = |policy: &RetryPolicy| {
+            let mut delays = policy.delays();
+            (1..5)
+                .map(|attempt| delays.next(attempt))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sequence(&decorrelated), sequence(&decorrelated));
+    }
+}
@@ -0,0 +1,82 @@
+use futures::Stream;
+
+use super::{query_post, Configuration, Error};
+
+/// Read every row of a query, one page at a time.
+///
+/// Each page is requested with the collection, arguments, fields, predicate and
+/// ordering of `request`, and successive offsets, starting from the request's own
+/// offset. The scan ends after the first page with fewer than `page_size` rows, or
+/// after the first error. The request should specify an ordering, so that pages
+/// are consistent with each other.
+pub fn scan(
+    configuration: Configuration,
+    request: ndc_models::QueryRequest,
+    page_size: u32,
+) -> impl Stream<Item = Result<ndc_models::RowSet, Error>> {
+    let page_size = page_size.max(1);
+    let offset = request.query.offset.unwrap_or(0);
+
+    futures::stream::unfold(
+        (configuration, request, Some(offset)),
+        move |(configuration, request, offset)| async move {
+            let offset = offset?;
+            let page = page_request(&request, offset, page_size);
+
+            let (result, next_offset) = match query_post(&configuration, page).await {
+                Ok(ndc_models::QueryResponse(row_sets)) => {
+                    let row_set = row_sets.into_iter().next().unwrap_or(ndc_models::RowSet {
+                        aggregates: None,
+                        rows: None,
+                    });
+                    let row_count = row_set.rows.as_ref().map_or(0, Vec::len);
+                    let next_offset = if row_count < page_size as usize {
+                        None
+                    } else {
+                        offset.checked_add(page_size)
+                    };
+                    (Ok(row_set), next_offset)
+                }
+                Err(err) => (Err(err), None),
+            };
+
+            Some((result, (configuration, request, next_offset)))
+        },
+    )
+}
+
+fn page_request(
+    request: &ndc_models::QueryRequest,
+    offset: u32,
+    page_size: u32,
+) -> ndc_models::QueryRequest {
+    let mut page = request.clone();
+    page.query.offset = Some(offset);
+    page.query.limit = Some(page_size);
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::page_request;
+
+    #[test]
+    fn test_page_request() {
+        let request: ndc_models::QueryRequest = serde_json::from_value(serde_json::json!({
+            "collection": "articles",
+            "query": {
+                "fields": { "id": { "type": "column", "column": "id" } },
+                "offset": 5
+            },
+            "arguments": {},
+            "collection_relationships": {}
+        }))
+        .unwrap();
+
+        let page = page_request(&request, 25, 10);
+        assert_eq!(page.query.offset, Some(25));
+        assert_eq!(page.query.limit, Some(10));
+        assert_eq!(page.query.fields, request.query.fields);
+        assert_eq!(page.collection, request.collection);
+    }
+}
@@ -0,0 +1,349 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::marker::PhantomData;
+
+use futures::Stream;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use tokio::sync::mpsc;
+
+use reqwest::header::HeaderMap;
+
+use super::{send_streaming, Configuration, Endpoint, Error, LiveResponse};
+
+/// One entry of a schema response, as yielded by [`schema_get_streaming`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaItem {
+    ScalarType(ndc_models::ScalarTypeName, ndc_models::ScalarType),
+    ObjectType(ndc_models::ObjectTypeName, ndc_models::ObjectType),
+    Collection(ndc_models::CollectionInfo),
+    Function(ndc_models::FunctionInfo),
+    Procedure(ndc_models::ProcedureInfo),
+}
+
+type ItemSender = mpsc::Sender<Result<SchemaItem, Error>>;
+
+/// Fetch the connector's schema, parsing it incrementally and yielding each scalar
+/// type, object type, collection, function and procedure as soon as it has been
+/// parsed.
+///
+/// Neither the response body nor the whole schema is held in memory at once, which
+/// makes this suitable for very large schemas. The body is only read as fast as
+/// items are consumed. For most schemas, [`super::schema_get`] is simpler.
+///
+/// The request is sent in the same way as a streamed mutation, with the same
+/// preflight, authentication and tracing. With strict deserialization, an unknown
+/// field is reported as a final error once the whole schema has been parsed.
+pub async fn schema_get_streaming(
+    configuration: &Configuration,
+) -> Result<impl Stream<Item = Result<SchemaItem, Error>>, Error> {
+    let LiveResponse { mut resp, guard } =
+        send_streaming(configuration, Endpoint::Schema, None, HeaderMap::new()).await?;
+    let strict = configuration.strict_deserialization;
+
+    let (chunk_sender, chunk_receiver) = mpsc::channel::<io::Result<Vec<u8>>>(4);
+    let (item_sender, item_receiver) = mpsc::channel(16);
+
+    // Read the body on the runtime, and hand each chunk to the parser
+    tokio::spawn(async move {
+        loop {
+            let chunk = match resp.chunk().await {
+                Ok(Some(chunk)) => Ok(chunk.to_vec()),
+                Ok(None) => break,
+                Err(err) => Err(io::Error::new(io::ErrorKind::Other, err)),
+            };
+            let failed = chunk.is_err();
+            if chunk_sender.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    // serde_json only parses incrementally from a blocking reader
+    tokio::task::spawn_blocking(move || {
+        let reader = ChunkReader {
+            receiver: chunk_receiver,
+            current: io::Cursor::new(vec![]),
+        };
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let seed = SchemaSeed {
+            sender: &item_sender,
+        };
+        let mut unknown_field = None;
+        let mut record_unknown = |path: serde_ignored::Path| {
+            unknown_field.get_or_insert_with(|| path.to_string());
+        };
+        let result = if strict {
+            seed.deserialize(serde_ignored::Deserializer::new(
+                &mut deserializer,
+                &mut record_unknown,
+            ))
+        } else {
+            seed.deserialize(&mut deserializer)
+        }
+        .and_then(|()| deserializer.end());
+        let result = match (result, unknown_field) {
+            (Err(err), _) => Err(Error::from(err)),
+            (Ok(()), Some(path)) => Err(Error::UnknownField { path }),
+            (Ok(()), None) => Ok(()),
+        };
+        if let Err(err) = result {
+            // If the consumer has gone away, there is nobody to report the error to
+            let _ = item_sender.blocking_send(Err(err));
+        }
+    });
+
+    Ok(futures::stream::unfold(
+        (item_receiver, guard),
+        |(mut receiver, guard)| async move {
+            let item = receiver.recv().await?;
+            Some((item, (receiver, guard)))
+        },
+    ))
+}
+
+/// Presents the chunks of a response body as a blocking reader
+struct ChunkReader {
+    receiver: mpsc::Receiver<io::Result<Vec<u8>>>,
+    current: io::Cursor<Vec<u8>>,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.current.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.current = io::Cursor::new(chunk?),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+fn send<E: de::Error>(sender: &ItemSender, item: SchemaItem) -> Result<(), E> {
+    sender
+        .blocking_send(Ok(item))
+        .map_err(|_| E::custom("schema stream was dropped"))
+}
+
+struct SchemaSeed<'a> {
+    sender: &'a ItemSender,
+}
+
+impl<'de> DeserializeSeed<'de> for SchemaSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SchemaSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a schema response")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            let sender = self.sender;
+            match key.as_str() {
+                "scalar_types" => map.next_value_seed(Entries {
+                    sender,
+                    wrap: SchemaItem::ScalarType,
+                    marker: PhantomData,
+                })?,
+                "object_types" => map.next_value_seed(Entries {
+                    sender,
+                    wrap: SchemaItem::ObjectType,
+                    marker: PhantomData,
+                })?,
+                "collections" => map.next_value_seed(Elements {
+                    sender,
+                    wrap: SchemaItem::Collection,
+                    marker: PhantomData,
+                })?,
+                "functions" => map.next_value_seed(Elements {
+                    sender,
+                    wrap: SchemaItem::Function,
+                    marker: PhantomData,
+                })?,
+                "procedures" => map.next_value_seed(Elements {
+                    sender,
+                    wrap: SchemaItem::Procedure,
+                    marker: PhantomData,
+                })?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sends each entry of a JSON object as a schema item
+struct Entries<'a, K, V> {
+    sender: &'a ItemSender,
+    wrap: fn(K, V) -> SchemaItem,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<'de, K: DeserializeOwned, V: DeserializeOwned> DeserializeSeed<'de> for Entries<'_, K, V> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K: DeserializeOwned, V: DeserializeOwned> Visitor<'de> for Entries<'_, K, V> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of schema definitions")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some((key, value)) = map.next_entry::<K, V>()? {
+            send(self.sender, (self.wrap)(key, value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Sends each element of a JSON array as a schema item
+struct Elements<'a, V> {
+    sender: &'a ItemSender,
+    wrap: fn(V) -> SchemaItem,
+    marker: PhantomData<V>,
+}
+
+impl<'de, V: DeserializeOwned> DeserializeSeed<'de> for Elements<'_, V> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, V: DeserializeOwned> Visitor<'de> for Elements<'_, V> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of schema definitions")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(value) = seq.next_element::<V>()? {
+            send(self.sender, (self.wrap)(value))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+
+    use futures::StreamExt;
+    use serde::de::DeserializeSeed;
+    use tokio::sync::mpsc;
+
+    use super::{schema_get_streaming, SchemaItem, SchemaSeed};
+    use crate::client::{schema_get, Configuration, Error};
+
+    #[test]
+    fn test_schema_seed() {
+        let schema = serde_json::json!({
+            "scalar_types": {
+                "Int": { "aggregate_functions": {}, "comparison_operators": {} }
+            },
+            "object_types": {},
+            "collections": [],
+            "functions": [],
+            "procedures": [{
+                "name": "delete_articles",
+                "arguments": {},
+                "result_type": { "type": "named", "name": "Int" }
+            }],
+            "unknown": [1, 2, 3]
+        });
+
+        let (sender, mut receiver) = mpsc::channel(16);
+        let bytes = serde_json::to_vec(&schema).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        SchemaSeed { sender: &sender }
+            .deserialize(&mut deserializer)
+            .unwrap();
+        drop(sender);
+
+        let mut items = vec![];
+        while let Ok(item) = receiver.try_recv() {
+            items.push(item.unwrap());
+        }
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[0], SchemaItem::ScalarType(name, _) if name.as_str() == "Int"));
+        assert!(
+            matches!(&items[1], SchemaItem::Procedure(info) if info.name.as_str() == "delete_articles")
+        );
+    }
+
+    /// Answer `requests` requests, each on a fresh connection, with `response`
+    fn serve(requests: usize, response: &'static str) -> reqwest::Url {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = reqwest::Url::parse(&format!("http://{}/", listener.local_addr().unwrap()));
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut reader = BufReader::new(stream.unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_schema_not_modified() {
+        let response = "HTTP/1.1 304 Not Modified\r\nconnection: close\r\n\r\n";
+        let configuration = Configuration::builder(serve(2, response)).build().unwrap();
+
+        assert!(matches!(
+            schema_get(&configuration).await,
+            Err(Error::NotModified)
+        ));
+        assert!(matches!(
+            schema_get_streaming(&configuration).await.err(),
+            Some(Error::NotModified)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_schema_streaming_strict() {
+        let response = "HTTP/1.1 200 OK\r\ncontent-length: 108\r\nconnection: close\r\n\r\n\
+            {\"scalar_types\": {}, \"object_types\": {}, \"collections\": [], \
+            \"functions\": [], \"procedures\": [], \"unknown\": 1}";
+        let configuration = Configuration::builder(serve(1, response))
+            .strict_deserialization(true)
+            .build()
+            .unwrap();
+
+        let items: Vec<_> = schema_get_streaming(&configuration)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(
+            items.as_slice(),
+            [Err(Error::UnknownField { path })] if path.contains("unknown")
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+
+use futures::Stream;
+
+use super::{mutation_post, Configuration, Error};
+
+type Relationships = BTreeMap<ndc_models::RelationshipName, ndc_models::Relationship>;
+
+/// A mutation operation failed part way through [`mutation_post_sequential`]
+#[derive(Debug)]
+pub struct SequentialMutationError {
+    /// The results of the operations which succeeded, before the failure
+    pub completed: Vec<ndc_models::MutationOperationResults>,
+    /// The index of the operation which failed
+    pub failed_index: usize,
+    pub error: Error,
+}
+
+impl fmt::Display for SequentialMutationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mutation operation {} failed after {} succeeded: {}",
+            self.failed_index,
+            self.completed.len(),
+            self.error
+        )
+    }
+}
+
+impl error::Error for SequentialMutationError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Progress through a mutation sent by [`mutation_post_with_progress`], emitted after
+/// each operation succeeds
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationProgress {
+    /// The number of operations which have succeeded so far
+    pub completed: usize,
+    /// The number of operations in the request
+    pub total: usize,
+    /// The result of the operation which just succeeded
+    pub last_result: ndc_models::MutationOperationResults,
+}
+
+/// Send each operation of a mutation request in its own request, in order, stopping
+/// at the first which fails.
+///
+/// This is **not atomic**: operations which succeeded before a failure stay applied.
+/// It is only intended for connectors which can't be trusted to stop at the first
+/// failure within a single request, where stopping early is better than continuing.
+/// Connectors which support transactional mutations should be sent the whole
+/// request with [`mutation_post`].
+pub async fn mutation_post_sequential(
+    configuration: &Configuration,
+    mutation_request: ndc_models::MutationRequest,
+) -> Result<ndc_models::MutationResponse, SequentialMutationError> {
+    let ndc_models::MutationRequest {
+        operations,
+        collection_relationships,
+    } = mutation_request;
+
+    let mut completed = Vec::with_capacity(operations.len());
+    for (index, operation) in operations.into_iter().enumerate() {
+        match send_operation(configuration, operation, &collection_relationships).await {
+            Ok(result) => completed.push(result),
+            Err(error) => {
+                return Err(SequentialMutationError {
+                    completed,
+                    failed_index: index,
+                    error,
+                })
+            }
+        }
+    }
+
+    Ok(ndc_models::MutationResponse {
+        operation_results: completed,
+    })
+}
+
+/// Send a mutation request in the same way as [`mutation_post_sequential`], yielding
+/// a [`MutationProgress`] after each operation, for example to show the progress of
+/// a long import.
+///
+/// If an operation fails, the stream yields a final error, which includes the
+/// results of the operations which succeeded, and ends. Operations are only sent
+/// as the stream is polled, so dropping it part way through stops the mutation,
+/// but does not undo the operations which have already succeeded.
+pub fn mutation_post_with_progress(
+    configuration: &Configuration,
+    mutation_request: ndc_models::MutationRequest,
+) -> impl Stream<Item = Result<MutationProgress, SequentialMutationError>> + '_ {
+    let ndc_models::MutationRequest {
+        operations,
+        collection_relationships,
+    } = mutation_request;
+    let total = operations.len();
+
+    futures::stream::unfold(
+        Some((
+            operations.into_iter(),
+            collection_relationships,
+            Vec::with_capacity(total),
+        )),
+        move |state| async move {
+            let (mut operations, collection_relationships, mut completed) = state?;
+            let operation = operations.next()?;
+            match send_operation(configuration, operation, &collection_relationships).await {
+                Ok(result) => {
+                    completed.push(result.clone());
+                    let progress = MutationProgress {
+                        completed: completed.len(),
+                        total,
+                        last_result: result,
+                    };
+                    Some((
+                        Ok(progress),
+                        Some((operations, collection_relationships, completed)),
+                    ))
+                }
+                Err(error) => {
+                    let failed_index = completed.len();
+                    let error = SequentialMutationError {
+                        completed,
+                        failed_index,
+                        error,
+                    };
+                    Some((Err(error), None))
+                }
+            }
+        },
+    )
+}
+
+/// Send a single operation in its own mutation request
+async fn send_operation(
+    configuration: &Configuration,
+    operation: ndc_models::MutationOperation,
+    collection_relationships: &Relationships,
+) -> Result<ndc_models::MutationOperationResults, Error> {
+    let request = ndc_models::MutationRequest {
+        operations: vec![operation],
+        collection_relationships: collection_relationships.clone(),
+    };
+    let response = mutation_post(configuration, request).await?;
+    single_result(response.operation_results)
+}
+
+/// The connector must return exactly one result for a request with one operation
+fn single_result(
+    mut results: Vec<ndc_models::MutationOperationResults>,
+) -> Result<ndc_models::MutationOperationResults, Error> {
+    match results.len() {
+        1 => Ok(results.remove(0)),
+        count => Err(Error::UnexpectedOperationResultCount { expected: 1, count }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::{mutation_post_with_progress, single_result};
+    use crate::client::{Configuration, Error};
+
+    #[test]
+    fn test_single_result() {
+        let result = || ndc_models::MutationOperationResults::Procedure {
+            result: serde_json::Value::Null,
+        };
+        assert_eq!(single_result(vec![result()]).unwrap(), result());
+        assert!(matches!(
+            single_result(vec![]),
+            Err(Error::UnexpectedOperationResultCount {
+                expected: 1,
+                count: 0
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mutation_progress_stops_at_failure() {
+        // Bind to find a free port, then close it so that nothing is listening
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let url = reqwest::Url::parse(&format!("http://{addr}")).unwrap();
+        let configuration = Configuration::builder(url).build().unwrap();
+        let operation = || ndc_models::MutationOperation::Procedure {
+            name: "delete_articles".into(),
+            arguments: std::collections::BTreeMap::new(),
+            fields: None,
+        };
+        let request = ndc_models::MutationRequest {
+            operations: vec![operation(), operation()],
+            collection_relationships: std::collections::BTreeMap::new(),
+        };
+
+        let events: Vec<_> = mutation_post_with_progress(&configuration, request)
+            .collect()
+            .await;
+        assert_eq!(events.len(), 1);
+        let error = events.into_iter().next().unwrap().unwrap_err();
+        assert_eq!(error.failed_index, 0);
+        assert!(error.completed.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+
+use super::{
+    capabilities_get, mutation_explain_post, mutation_post, query_explain_post, query_post,
+    schema_get, Configuration, Endpoint, Error,
+};
+
+/// A request to any of the connector's endpoints
+#[derive(Debug, Clone)]
+pub enum NdcRequest {
+    Capabilities,
+    Schema,
+    Query(ndc_models::QueryRequest),
+    QueryExplain(ndc_models::QueryRequest),
+    Mutation(ndc_models::MutationRequest),
+    MutationExplain(ndc_models::MutationRequest),
+}
+
+impl NdcRequest {
+    pub fn endpoint(&self) -> Endpoint {
+        match self {
+            NdcRequest::Capabilities => Endpoint::Capabilities,
+            NdcRequest::Schema => Endpoint::Schema,
+            NdcRequest::Query(_) => Endpoint::Query,
+            NdcRequest::QueryExplain(_) => Endpoint::QueryExplain,
+            NdcRequest::Mutation(_) => Endpoint::Mutation,
+            NdcRequest::MutationExplain(_) => Endpoint::MutationExplain,
+        }
+    }
+}
+
+/// The response to an [`NdcRequest`]
+#[derive(Debug, Clone)]
+pub enum NdcResponse {
+    Capabilities(ndc_models::CapabilitiesResponse),
+    Schema(ndc_models::SchemaResponse),
+    Query(ndc_models::QueryResponse),
+    Mutation(ndc_models::MutationResponse),
+    Explain(ndc_models::ExplainResponse),
+}
+
+/// Exposes a connector as a [`tower::Service`], so that it can be composed with
+/// tower middleware such as timeouts, retries and concurrency limits
+#[derive(Debug, Clone)]
+pub struct NdcService {
+    configuration: Configuration,
+}
+
+impl NdcService {
+    pub fn new(configuration: Configuration) -> Self {
+        Self { configuration }
+    }
+
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+}
+
+impl tower::Service<NdcRequest> for NdcService {
+    type Response = NdcResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<NdcResponse, Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: NdcRequest) -> Self::Future {
+        let configuration = self.configuration.clone();
+        Box::pin(async move {
+            let configuration = &configuration;
+            match request {
+                NdcRequest::Capabilities => capabilities_get(configuration)
+                    .await
+                    .map(NdcResponse::Capabilities),
+                NdcRequest::Schema => schema_get(configuration).await.map(NdcResponse::Schema),
+                NdcRequest::Query(request) => query_post(configuration, request)
+                    .await
+                    .map(NdcResponse::Query),
+                NdcRequest::QueryExplain(request) => query_explain_post(configuration, request)
+                    .await
+                    .map(NdcResponse::Explain),
+                NdcRequest::Mutation(request) => mutation_post(configuration, request)
+                    .await
+                    .map(NdcResponse::Mutation),
+                NdcRequest::MutationExplain(request) => {
+                    mutation_explain_post(configuration, request)
+                        .await
+                        .map(NdcResponse::Explain)
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+
+use super::{query_post, request_fingerprint, Configuration, Error};
+
+pub type SharedQueryResult = Arc<Result<ndc_models::QueryResponse, Error>>;
+
+type InFlight = Mutex<HashMap<u64, broadcast::Sender<SharedQueryResult>>>;
+
+/// Wraps a [`Configuration`] so that concurrent, identical query requests share a
+/// single request to the connector. Every caller waiting on the same request
+/// receives a shared reference to the same result.
+#[derive(Debug, Clone)]
+pub struct SingleflightClient {
+    configuration: Configuration,
+    in_flight: Arc<InFlight>,
+}
+
+impl SingleflightClient {
+    pub fn new(configuration: Configuration) -> Self {
+        Self {
+            configuration,
+            in_flight: Arc::default(),
+        }
+    }
+
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    pub async fn query_post(&self, query_request: ndc_models::QueryRequest) -> SharedQueryResult {
+        let key = match request_fingerprint(&query_request) {
+            Ok(key) => key,
+            Err(err) => return Arc::new(Err(err)),
+        };
+
+        loop {
+            let mut receiver = {
+                let mut in_flight = self.in_flight.lock().unwrap();
+                match in_flight.entry(key) {
+                    Entry::Occupied(entry) => entry.get().subscribe(),
+                    Entry::Vacant(entry) => {
+                        let (sender, _) = broadcast::channel(1);
+                        entry.insert(sender);
+                        break;
+                    }
+                }
+            };
+
+            // If the leading request was cancelled before it completed, the sender
+            // is dropped without a result, and we try again.
+            if let Ok(result) = receiver.recv().await {
+                return result;
+            }
+        }
+
+        let guard = InFlightGuard {
+            in_flight: &self.in_flight,
+            key,
+            armed: true,
+        };
+
+        let result = Arc::new(query_post(&self.configuration, query_request).await);
+        guard.complete(&result);
+        result
+    }
+}
+
+/// Removes an in-flight entry when the leading request completes, or when it is
+/// dropped before completing, so that waiting callers are never left hanging.
+struct InFlightGuard<'a> {
+    in_flight: &'a InFlight,
+    key: u64,
+    armed: bool,
+}
+
+impl InFlightGuard<'_> {
+    fn complete(mut self, result: &SharedQueryResult) {
+        self.armed = false;
+        let sender = self.in_flight.lock().unwrap().remove(&self.key);
+        if let Some(sender) = sender {
+            // There may be no receivers, if no other caller joined this request
+            let _ = sender.send(result.clone());
+        }
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.in_flight.lock().unwrap().remove(&self.key);
+        }
+    }
+}
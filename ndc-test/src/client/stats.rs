@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+/// Counts the requests currently being made to a connector, and the most that
+/// have been in flight at once
+#[derive(Debug, Default)]
+pub struct RequestStats {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    /// Notified whenever the number of requests in flight drops to zero
+    idle: Notify,
+}
+
+impl RequestStats {
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Wait until no requests are in flight, for at most `timeout`, returning the
+    /// number still in flight when it elapsed. Requests which start while waiting are
+    /// also waited for.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let idle = async {
+            loop {
+                // Register for the notification before checking, so that a request
+                // completing in between is not missed
+                let notified = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        let _ = tokio::time::timeout(timeout, idle).await;
+        self.in_flight()
+    }
+
+    /// Count a request as in flight until the returned guard is dropped
+    pub(crate) fn start(self: &Arc<Self>) -> RequestGuard {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::Relaxed);
+        RequestGuard(self.clone())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RequestGuard(Arc<RequestStats>);
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::RequestStats;
+
+    #[test]
+    fn test_request_stats() {
+        let stats = Arc::new(RequestStats::default());
+
+        let first = stats.start();
+        let second = stats.start();
+        assert_eq!(stats.in_flight(), 2);
+
+        drop(first);
+        assert_eq!(stats.in_flight(), 1);
+
+        drop(second);
+        let _third = stats.start();
+        assert_eq!(stats.in_flight(), 1);
+        assert_eq!(stats.max_in_flight(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_drain() {
+        let stats = Arc::new(RequestStats::default());
+        assert_eq!(stats.drain(Duration::from_millis(10)).await, 0);
+
+        let guard = stats.start();
+        assert_eq!(stats.drain(Duration::from_millis(10)).await, 1);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(guard);
+        });
+        assert_eq!(stats.drain(Duration::from_secs(5)).await, 0);
+    }
+}
@@ -0,0 +1,139 @@
+use serde::de::DeserializeOwned;
+
+use super::Error;
+
+/// Reads newline-delimited JSON values from a response body, one chunk at a time.
+///
+/// Body chunks are only read when the buffered data does not already contain a
+/// complete value, so a slow consumer applies backpressure to the connection
+/// rather than causing the whole body to be buffered in memory. Once an error has
+/// been returned, no further values are read.
+#[derive(Debug)]
+pub(crate) struct NdjsonReader {
+    response: Option<reqwest::Response>,
+    buffer: Vec<u8>,
+}
+
+impl NdjsonReader {
+    pub(crate) fn new(response: reqwest::Response) -> Self {
+        Self {
+            response: Some(response),
+            buffer: vec![],
+        }
+    }
+
+    /// Read the next value, or `None` if the body has been fully consumed
+    pub(crate) async fn next_value<T: DeserializeOwned>(&mut self) -> Option<Result<T, Error>> {
+        loop {
+            if let Some(newline) = self.buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+                if !is_blank(&line) {
+                    return Some(self.parse(&line));
+                }
+                continue;
+            }
+
+            let response = self.response.as_mut()?;
+
+            match response.chunk().await {
+                Ok(Some(chunk)) => self.buffer.extend_from_slice(&chunk),
+                Ok(None) => {
+                    // The last value may not be followed by a newline
+                    self.response = None;
+                    let line = std::mem::take(&mut self.buffer);
+                    return if is_blank(&line) {
+                        None
+                    } else {
+                        Some(self.parse(&line))
+                    };
+                }
+                Err(err) => {
+                    self.terminate();
+                    return Some(Err(Error::from(err)));
+                }
+            }
+        }
+    }
+
+    fn parse<T: DeserializeOwned>(&mut self, line: &[u8]) -> Result<T, Error> {
+        serde_json::from_slice(line).map_err(|err| {
+            self.terminate();
+            Error::from(err)
+        })
+    }
+
+    fn terminate(&mut self) {
+        self.response = None;
+        self.buffer.clear();
+    }
+}
+
+fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+
+    use super::*;
+
+    fn reader(body: &'static str) -> NdjsonReader {
+        let response = http::Response::builder().status(200).body(body).unwrap();
+        NdjsonReader::new(response.into())
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_reader() {
+        let mut reader = reader("{\"result\":1}\n\n{\"result\":2}");
+
+        let first: serde_json::Value = reader.next_value().await.unwrap().unwrap();
+        assert_eq!(first, serde_json::json!({"result": 1}));
+        let second: serde_json::Value = reader.next_value().await.unwrap().unwrap();
+        assert_eq!(second, serde_json::json!({"result": 2}));
+        assert!(reader.next_value::<serde_json::Value>().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_reader_terminates_on_error() {
+        let mut reader = reader("{\"result\":1}\n{\"result\n{\"result\":3}\n");
+
+        assert!(reader
+            .next_value::<serde_json::Value>()
+            .await
+            .unwrap()
+            .is_ok());
+        assert!(reader
+            .next_value::<serde_json::Value>()
+            .await
+            .unwrap()
+            .is_err());
+        assert!(reader.next_value::<serde_json::Value>().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_reader_reads_lazily() {
+        let chunks_read = Arc::new(AtomicUsize::new(0));
+        let counter = chunks_read.clone();
+        let body = futures::stream::iter(0..1000).map(move |i| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, std::io::Error>(format!("{{\"result\":{i}}}\n"))
+        });
+        let response = http::Response::builder()
+            .status(200)
+            .body(reqwest::Body::wrap_stream(body))
+            .unwrap();
+        let mut reader = NdjsonReader::new(response.into());
+
+        for expected in 0..2 {
+            let value: serde_json::Value = reader.next_value().await.unwrap().unwrap();
+            assert_eq!(value, serde_json::json!({ "result": expected }));
+        }
+
+        // Only the chunks needed for the values consumed so far have been read
+        assert_eq!(chunks_read.load(Ordering::SeqCst), 2);
+    }
+}
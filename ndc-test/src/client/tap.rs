@@ -0,0 +1,15 @@
+use std::fmt;
+
+/// Observes the exact bytes of request and response bodies, for example in a proxy
+/// which needs to log or forward them faithfully. Both callbacks default to doing
+/// nothing.
+///
+/// Request bodies are tapped after serialization, and response bodies before they
+/// are verified or parsed, so the bytes are those sent and received on the wire.
+/// A request body is tapped once per call, when it is first sent, even if the
+/// request is retried or sent again with another credential.
+pub trait BodyTap: fmt::Debug + Send + Sync {
+    fn on_request_body(&self, _body: &[u8]) {}
+
+    fn on_response_body(&self, _body: &[u8]) {}
+}
@@ -0,0 +1,66 @@
+use std::future::Future;
+use std::time::Instant;
+
+use tracing::Instrument;
+
+use super::{endpoint_url, Configuration, Endpoint, Error, LiveResponse, WithMetadata};
+
+/// The result of a successful call, which records its status on the span
+pub(crate) trait Traced {
+    fn status(&self) -> reqwest::StatusCode;
+}
+
+impl<T> Traced for WithMetadata<T> {
+    fn status(&self) -> reqwest::StatusCode {
+        self.metadata.status
+    }
+}
+
+impl Traced for LiveResponse {
+    fn status(&self) -> reqwest::StatusCode {
+        self.resp.status()
+    }
+}
+
+/// Run a call to the connector, including any retries, inside an `ndc_request`
+/// span, and record its outcome on the span once it completes. For a streamed
+/// response, the span ends once the status has been checked.
+///
+/// This is independent of any other instrumentation, so it works with any
+/// `tracing` subscriber, with or without an OpenTelemetry layer.
+pub(crate) async fn traced<T: Traced>(
+    configuration: &Configuration,
+    endpoint: Endpoint,
+    call: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    let span = tracing::info_span!(
+        "ndc_request",
+        endpoint = %endpoint,
+        url = tracing::field::Empty,
+        status = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+        error.kind = tracing::field::Empty,
+    );
+    if let Ok(url) = endpoint_url(configuration, endpoint) {
+        span.record("url", tracing::field::display(url));
+    }
+
+    let start = Instant::now();
+    let result = call.instrument(span.clone()).await;
+
+    let status = match &result {
+        Ok(response) => Some(response.status()),
+        Err(err) => {
+            span.record("error.kind", err.kind());
+            err.status()
+        }
+    };
+    if let Some(status) = status {
+        span.record("status", status.as_u16());
+    }
+    span.record(
+        "duration_ms",
+        u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+    );
+    result
+}
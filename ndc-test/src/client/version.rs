@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use tokio::sync::OnceCell;
+
+use super::{capabilities_get, Configuration, Error};
+
+/// Caches the specification version reported by the connector, shared by every
+/// clone of a configuration
+pub type SpecVersionCache = Arc<OnceCell<semver::Version>>;
+
+/// The version of the specification which the connector implements, as reported by
+/// its capabilities. The version is fetched once and cached for the lifetime of the
+/// configuration; failures are not cached.
+pub async fn detected_spec_version(
+    configuration: &Configuration,
+) -> Result<semver::Version, Error> {
+    configuration
+        .spec_version_cache
+        .get_or_try_init(|| async {
+            let capabilities = capabilities_get(configuration).await?;
+            parse_spec_version(&capabilities.version)
+        })
+        .await
+        .cloned()
+}
+
+fn parse_spec_version(version: &str) -> Result<semver::Version, Error> {
+    semver::Version::parse(version.trim()).map_err(|e| Error::InvalidSpecVersion {
+        version: version.into(),
+        reason: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_spec_version;
+    use crate::client::Error;
+
+    #[test]
+    fn test_parse_spec_version() {
+        assert_eq!(
+            parse_spec_version(" 0.1.6 ").unwrap(),
+            semver::Version::new(0, 1, 6)
+        );
+        assert!(matches!(
+            parse_spec_version("v0.1"),
+            Err(Error::InvalidSpecVersion { version, .. }) if version == "v0.1"
+        ));
+    }
+}
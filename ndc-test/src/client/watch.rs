@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use futures::Stream;
+
+use super::{schema_get, Configuration, Error};
+
+/// Poll the connector's schema every `interval`, yielding the schema when it is
+/// first fetched and then each time it changes. Failed polls are yielded as errors,
+/// and polling continues.
+pub fn watch_schema(
+    configuration: Configuration,
+    interval: Duration,
+) -> impl Stream<Item = Result<ndc_models::SchemaResponse, Error>> {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    futures::stream::unfold(
+        (configuration, ticker, SchemaChanges::default()),
+        |(configuration, mut ticker, mut changes)| async move {
+            loop {
+                ticker.tick().await;
+                let result = match schema_get(&configuration).await {
+                    Ok(schema) if changes.observe(&schema) => Ok(schema),
+                    Ok(_) => continue,
+                    Err(err) => Err(err),
+                };
+                return Some((result, (configuration, ticker, changes)));
+            }
+        },
+    )
+}
+
+/// Tracks the [`ndc_models::SchemaResponse::hash`] of the last schema seen
+#[derive(Debug, Default)]
+struct SchemaChanges {
+    last: Option<String>,
+}
+
+impl SchemaChanges {
+    /// Record `schema`, returning whether it differs from the last schema recorded
+    fn observe(&mut self, schema: &ndc_models::SchemaResponse) -> bool {
+        let hash = schema.hash();
+        let changed = self.last.as_ref() != Some(&hash);
+        self.last = Some(hash);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaChanges;
+
+    #[test]
+    fn test_schema_changes() {
+        let mut changes = SchemaChanges::default();
+        let mut schema = ndc_models::SchemaResponse::default();
+
+        assert!(changes.observe(&schema));
+        assert!(!changes.observe(&schema));
+
+        schema.procedures.push(ndc_models::ProcedureInfo {
+            name: "upsert".into(),
+            description: None,
+            arguments: std::collections::BTreeMap::new(),
+            result_type: ndc_models::Type::Named { name: "Int".into() },
+        });
+        assert!(changes.observe(&schema));
+        assert!(!changes.observe(&schema));
+    }
+}
@@ -48,6 +48,11 @@ pub enum Error {
     UnexpectedField(ndc_models::FieldName),
     #[error("scalar type {0} has multiple equality operators")]
     MultipleEqualityOperators(ndc_models::ScalarTypeName),
+    #[error(
+        "schema is not internally consistent: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    InvalidSchema(Vec<ndc_models::SchemaError>),
     #[error("error response from connector: {0:?}")]
     ConnectorError(ndc_models::ErrorResponse),
     #[error("cannot open snapshot file: {0:?}")]
@@ -5,9 +5,12 @@ pub mod client;
 pub mod configuration;
 pub mod connector;
 pub mod error;
+pub mod offline;
 pub mod reporter;
 pub mod snapshot;
 pub mod test_cases;
+#[cfg(feature = "test-server")]
+pub mod test_server;
 
 use std::collections::BTreeMap;
 use std::fs::File;
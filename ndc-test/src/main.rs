@@ -134,10 +134,7 @@ async fn main() {
                 gen_config,
             };
 
-            let configuration = Configuration {
-                base_path: endpoint,
-                client: reqwest::Client::new(),
-            };
+            let configuration = Configuration::builder(endpoint).build().unwrap();
 
             let mut reporter = (ConsoleReporter::default(), TestResults::default());
 
@@ -155,10 +152,7 @@ async fn main() {
             snapshots_dir,
             no_validate_responses,
         } => {
-            let configuration = Configuration {
-                base_path: endpoint,
-                client: reqwest::Client::new(),
-            };
+            let configuration = Configuration::builder(endpoint).build().unwrap();
 
             let mut reporter = (ConsoleReporter::default(), TestResults::default());
 
@@ -187,10 +181,7 @@ async fn main() {
             samples,
             tolerance,
         } => {
-            let configuration = Configuration {
-                base_path: endpoint,
-                client: reqwest::Client::new(),
-            };
+            let configuration = Configuration::builder(endpoint).build().unwrap();
 
             let mut reporter = (ConsoleReporter::default(), TestResults::default());
 
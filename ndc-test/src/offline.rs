@@ -0,0 +1,205 @@
+//! Validation of requests and schemas which does not need a running connector, for
+//! use in build-time tooling and tests.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ndc_models as models;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RequestError {
+    #[error("collection name is empty")]
+    EmptyCollectionName,
+    #[error("procedure name is empty in operation {0}")]
+    EmptyProcedureName(usize),
+    #[error("variable set {0} does not define the same variables as the first variable set")]
+    InconsistentVariables(usize),
+    #[error("variable {0} is not defined")]
+    VariableIsNotDefined(models::VariableName),
+    #[error("relationship {0} is not defined in request")]
+    RelationshipIsNotDefined(models::RelationshipName),
+}
+
+/// Check that a query request is well-formed: the collection name is not empty,
+/// every variable set defines the same variables, any variables used anywhere in the
+/// request are defined, and any relationships used in fields are defined.
+pub fn validate_query_request(request: &models::QueryRequest) -> Result<(), Vec<RequestError>> {
+    let mut errors = vec![];
+
+    if request.collection.as_str().is_empty() {
+        errors.push(RequestError::EmptyCollectionName);
+    }
+
+    let variable_names: BTreeSet<&models::VariableName> = request
+        .variables
+        .iter()
+        .flatten()
+        .next()
+        .map(|variables| variables.keys().collect())
+        .unwrap_or_default();
+    for (index, variables) in request.variables.iter().flatten().enumerate().skip(1) {
+        if variables.keys().collect::<BTreeSet<_>>() != variable_names {
+            errors.push(RequestError::InconsistentVariables(index));
+        }
+    }
+
+    for name in models::referenced_variables(request) {
+        if !variable_names.contains(&name) {
+            errors.push(RequestError::VariableIsNotDefined(name));
+        }
+    }
+
+    validate_query_relationships(
+        &request.query,
+        &request.collection_relationships,
+        &mut errors,
+    );
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_query_relationships(
+    query: &models::Query,
+    collection_relationships: &BTreeMap<models::RelationshipName, models::Relationship>,
+    errors: &mut Vec<RequestError>,
+) {
+    for field in query.fields.iter().flat_map(|fields| fields.values()) {
+        if let models::Field::Relationship {
+            query,
+            relationship,
+            ..
+        } = field
+        {
+            if !collection_relationships.contains_key(relationship) {
+                errors.push(RequestError::RelationshipIsNotDefined(relationship.clone()));
+            }
+            validate_query_relationships(query, collection_relationships, errors);
+        }
+    }
+}
+
+/// Check that a mutation request is well-formed: every operation names a procedure
+pub fn validate_mutation_request(
+    request: &models::MutationRequest,
+) -> Result<(), Vec<RequestError>> {
+    let errors: Vec<RequestError> = request
+        .operations
+        .iter()
+        .enumerate()
+        .filter_map(|(index, operation)| match operation {
+            models::MutationOperation::Procedure { name, .. } => name
+                .as_str()
+                .is_empty()
+                .then_some(RequestError::EmptyProcedureName(index)),
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that a schema is internally consistent, as [`models::validate_schema`] does
+pub fn validate_schema(schema: &models::SchemaResponse) -> Result<(), Vec<models::SchemaError>> {
+    models::validate_schema(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_request(request: serde_json::Value) -> models::QueryRequest {
+        serde_json::from_value(request).unwrap()
+    }
+
+    #[test]
+    fn test_validate_query_request() {
+        let request = query_request(serde_json::json!({
+            "collection": "articles_by_author",
+            "query": {
+                "fields": {
+                    "author": {
+                        "type": "relationship",
+                        "relationship": "article_author",
+                        "arguments": {},
+                        "query": { "fields": {} }
+                    }
+                }
+            },
+            "arguments": { "author_id": { "type": "variable", "name": "id" } },
+            "collection_relationships": {},
+            "variables": [{ "id": 1 }, { "other": 2 }]
+        }));
+
+        assert_eq!(
+            validate_query_request(&request),
+            Err(vec![
+                RequestError::InconsistentVariables(1),
+                RequestError::RelationshipIsNotDefined("article_author".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_query_request_without_variables() {
+        let request = query_request(serde_json::json!({
+            "collection": "",
+            "query": {},
+            "arguments": { "author_id": { "type": "variable", "name": "id" } },
+            "collection_relationships": {}
+        }));
+
+        assert_eq!(
+            validate_query_request(&request),
+            Err(vec![
+                RequestError::EmptyCollectionName,
+                RequestError::VariableIsNotDefined("id".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_query_request_nested_variable() {
+        let request = query_request(serde_json::json!({
+            "collection": "authors",
+            "query": {
+                "fields": {
+                    "articles": {
+                        "type": "relationship",
+                        "relationship": "author_articles",
+                        "arguments": {},
+                        "query": {
+                            "fields": {},
+                            "predicate": {
+                                "type": "binary_comparison_operator",
+                                "column": { "type": "column", "name": "title", "path": [] },
+                                "operator": "like",
+                                "value": { "type": "variable", "name": "search" }
+                            }
+                        }
+                    }
+                }
+            },
+            "arguments": {},
+            "collection_relationships": {
+                "author_articles": {
+                    "column_mapping": { "id": "author_id" },
+                    "relationship_type": "array",
+                    "target_collection": "articles",
+                    "arguments": {}
+                }
+            },
+            "variables": [{ "id": 1 }]
+        }));
+
+        assert_eq!(
+            validate_query_request(&request),
+            Err(vec![RequestError::VariableIsNotDefined("search".into())])
+        );
+    }
+}
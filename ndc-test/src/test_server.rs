@@ -0,0 +1,196 @@
+//! A minimal connector served over real HTTP, for integration tests which need
+//! genuine HTTP behavior, such as timeouts and chunked bodies.
+//!
+//! The server binds to an ephemeral port on the loopback interface, and implements
+//! every endpoint of the specification with canned, spec-valid responses.
+
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tokio::sync::oneshot;
+
+use crate::client;
+use crate::models;
+
+/// The responses served by a [`TestServer`]. The defaults describe a connector with
+/// no capabilities beyond the required ones and an empty schema.
+#[derive(Debug, Clone)]
+pub struct TestResponses {
+    pub capabilities: models::CapabilitiesResponse,
+    pub schema: models::SchemaResponse,
+    /// Served for every query. If `None`, each query is answered with one empty row
+    /// set for each set of variables.
+    pub query: Option<models::QueryResponse>,
+    pub explain: models::ExplainResponse,
+}
+
+impl Default for TestResponses {
+    fn default() -> Self {
+        Self {
+            capabilities: client::default_capabilities(),
+            schema: models::SchemaResponse {
+                scalar_types: BTreeMap::new(),
+                object_types: BTreeMap::new(),
+                collections: vec![],
+                functions: vec![],
+                procedures: vec![],
+            },
+            query: None,
+            explain: models::ExplainResponse {
+                details: BTreeMap::new(),
+            },
+        }
+    }
+}
+
+/// A connector running in the current process, which shuts down when dropped
+#[derive(Debug)]
+pub struct TestServer {
+    addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl TestServer {
+    /// Start a server with the default responses
+    pub fn start() -> std::io::Result<Self> {
+        Self::start_with(TestResponses::default())
+    }
+
+    /// Start a server with the given responses. Must be called from within a Tokio
+    /// runtime.
+    pub fn start_with(responses: TestResponses) -> std::io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let app = Router::new()
+            .route("/health", get(|| async {}))
+            .route("/capabilities", get(get_capabilities))
+            .route("/schema", get(get_schema))
+            .route("/query", post(post_query))
+            .route("/query/explain", post(post_explain))
+            .route("/mutation", post(post_mutation))
+            .route("/mutation/explain", post(post_explain))
+            .with_state(Arc::new(responses));
+
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        let server = axum::Server::from_tcp(listener)
+            .map_err(std::io::Error::other)?
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(async {
+                let _ = shutdown_signal.await;
+            });
+        tokio::spawn(server);
+
+        Ok(Self {
+            addr,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn base_url(&self) -> reqwest::Url {
+        reqwest::Url::parse(&format!("http://{}/", self.addr))
+            .expect("socket addresses are valid hosts")
+    }
+
+    /// A configuration for a client of this server
+    pub fn configuration(&self) -> client::ConfigurationBuilder {
+        client::Configuration::builder(self.base_url())
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+type Responses = State<Arc<TestResponses>>;
+
+async fn get_capabilities(State(responses): Responses) -> Json<models::CapabilitiesResponse> {
+    Json(responses.capabilities.clone())
+}
+
+async fn get_schema(State(responses): Responses) -> Json<models::SchemaResponse> {
+    Json(responses.schema.clone())
+}
+
+async fn post_query(
+    State(responses): Responses,
+    Json(request): Json<models::QueryRequest>,
+) -> Json<models::QueryResponse> {
+    if let Some(response) = &responses.query {
+        return Json(response.clone());
+    }
+    let row_sets = request.variables.as_ref().map_or(1, Vec::len);
+    let row_set = models::RowSet {
+        aggregates: None,
+        rows: Some(vec![]),
+    };
+    Json(models::QueryResponse(vec![row_set; row_sets]))
+}
+
+async fn post_explain(State(responses): Responses) -> Json<models::ExplainResponse> {
+    Json(responses.explain.clone())
+}
+
+async fn post_mutation(
+    Json(request): Json<models::MutationRequest>,
+) -> Json<models::MutationResponse> {
+    let operation_results = request
+        .operations
+        .iter()
+        .map(|_| models::MutationOperationResults::Procedure {
+            result: serde_json::Value::Null,
+        })
+        .collect();
+    Json(models::MutationResponse { operation_results })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::TestServer;
+    use crate::client;
+
+    #[tokio::test]
+    async fn test_server_responses() {
+        let server = TestServer::start().unwrap();
+        let configuration = server.configuration().build().unwrap();
+
+        let capabilities = client::capabilities_get(&configuration).await.unwrap();
+        assert_eq!(capabilities, client::default_capabilities());
+
+        let response = client::query_post(
+            &configuration,
+            ndc_models::QueryRequest {
+                collection: "articles".into(),
+                query: ndc_models::Query {
+                    aggregates: None,
+                    fields: None,
+                    limit: None,
+                    offset: None,
+                    order_by: None,
+                    predicate: None,
+                },
+                arguments: BTreeMap::new(),
+                collection_relationships: BTreeMap::new(),
+                variables: Some(vec![BTreeMap::new(); 2]),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.0.len(), 2);
+    }
+}